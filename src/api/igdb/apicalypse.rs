@@ -0,0 +1,226 @@
+// Construction des corps de requête IGDB (langage Apicalypse).
+// Les clauses sont toujours émises dans le même ordre:
+// search, fields, exclude, where, sort, limit, offset.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ordre {
+    Croissant,
+    #[allow(dead_code)]
+    Decroissant,
+}
+
+impl Ordre {
+    fn code(&self) -> &'static str {
+        match self {
+            Ordre::Croissant => "asc",
+            Ordre::Decroissant => "desc",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Apicalypse {
+    recherche: Option<String>,
+    champs: Vec<String>,
+    exclusions: Vec<String>,
+    conditions: Vec<String>,
+    tri: Option<(String, Ordre)>,
+    limite: Option<u32>,
+    decalage: Option<u32>,
+}
+
+pub fn echapper(texte: &str) -> String {
+    texte.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn liste(ids: &[u32]) -> String {
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    format!("({})", ids.join(","))
+}
+
+impl Apicalypse {
+    pub fn new() -> Self {
+        Apicalypse::default()
+    }
+
+    pub fn rechercher(mut self, texte: &str) -> Self {
+        self.recherche = Some(String::from(texte));
+        self
+    }
+
+    pub fn champs(mut self, champs: &[&str]) -> Self {
+        self.champs.extend(champs.iter().map(|champ| String::from(*champ)));
+        self
+    }
+
+    // Inclut l'objet lié complet plutôt que son identifiant (ex: cover.*).
+    pub fn developper(mut self, champs: &[&str]) -> Self {
+        self.champs.extend(champs.iter().map(|champ| format!("{}.*", champ)));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn exclure(mut self, champs: &[&str]) -> Self {
+        self.exclusions.extend(champs.iter().map(|champ| String::from(*champ)));
+        self
+    }
+
+    // Condition brute, combinée aux autres par un ET.
+    pub fn filtrer(mut self, condition: &str) -> Self {
+        self.conditions.push(String::from(condition));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn egal(self, champ: &str, valeur: u32) -> Self {
+        self.filtrer(&format!("{} = {}", champ, valeur))
+    }
+
+    pub fn parmi(self, champ: &str, valeurs: &[u32]) -> Self {
+        self.filtrer(&format!("{} = {}", champ, liste(valeurs)))
+    }
+
+    // Au moins un des champs contient l'une des valeurs.
+    pub fn parmi_l_un(self, champs: &[&str], valeurs: &[u32]) -> Self {
+        let conditions: Vec<String> = champs.iter()
+            .map(|champ| format!("{} = {}", champ, liste(valeurs)))
+            .collect();
+        self.filtrer(&format!("({})", conditions.join(" | ")))
+    }
+
+    #[allow(dead_code)]
+    pub fn texte_egal(self, champ: &str, valeur: &str) -> Self {
+        self.filtrer(&format!("{} = \"{}\"", champ, echapper(valeur)))
+    }
+
+    pub fn trier(mut self, champ: &str, ordre: Ordre) -> Self {
+        self.tri = Some((String::from(champ), ordre));
+        self
+    }
+
+    pub fn limiter(mut self, limite: u32) -> Self {
+        self.limite = Some(limite);
+        self
+    }
+
+    pub fn decaler(mut self, decalage: u32) -> Self {
+        self.decalage = Some(decalage);
+        self
+    }
+
+    pub fn construire(&self) -> String {
+        let mut clauses: Vec<String> = vec![];
+
+        if let Some(recherche) = &self.recherche {
+            clauses.push(format!("search \"{}\";", echapper(recherche)));
+        }
+
+        match self.champs.is_empty() {
+            true => clauses.push(String::from("fields *;")),
+            false => clauses.push(format!("fields {};", self.champs.join(","))),
+        }
+
+        if !self.exclusions.is_empty() {
+            clauses.push(format!("exclude {};", self.exclusions.join(",")));
+        }
+
+        if !self.conditions.is_empty() {
+            clauses.push(format!("where {};", self.conditions.join(" & ")));
+        }
+
+        if let Some((champ, ordre)) = &self.tri {
+            clauses.push(format!("sort {} {};", champ, ordre.code()));
+        }
+
+        if let Some(limite) = self.limite {
+            clauses.push(format!("limit {};", limite));
+        }
+
+        if let Some(decalage) = self.decalage {
+            clauses.push(format!("offset {};", decalage));
+        }
+
+        clauses.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corps_vide() {
+        assert_eq!(Apicalypse::new().construire(), "fields *;");
+    }
+
+    #[test]
+    fn ordre_des_clauses() {
+        let corps = Apicalypse::new()
+            .decaler(20)
+            .limiter(10)
+            .trier("rating", Ordre::Decroissant)
+            .egal("category", 0)
+            .exclure(&["storyline"])
+            .champs(&["name", "slug"])
+            .rechercher("Zelda")
+            .construire();
+
+        assert_eq!(
+            corps,
+            "search \"Zelda\"; fields name,slug; exclude storyline; where category = 0; sort rating desc; limit 10; offset 20;",
+        );
+    }
+
+    #[test]
+    fn recherche_echappee() {
+        let corps = Apicalypse::new()
+            .rechercher("Assassin's \"Creed\" \\ Unity")
+            .limiter(1)
+            .construire();
+
+        assert_eq!(corps, "search \"Assassin's \\\"Creed\\\" \\\\ Unity\"; fields *; limit 1;");
+    }
+
+    #[test]
+    fn developpements() {
+        let corps = Apicalypse::new()
+            .champs(&["name"])
+            .developper(&["cover", "genres"])
+            .construire();
+
+        assert_eq!(corps, "fields name,cover.*,genres.*;");
+    }
+
+    #[test]
+    fn conditions_combinees() {
+        let corps = Apicalypse::new()
+            .parmi("platforms", &[18, 19])
+            .texte_egal("slug", "super-mario-\"64\"")
+            .filtrer("rating > 80")
+            .construire();
+
+        assert_eq!(
+            corps,
+            "fields *; where platforms = (18,19) & slug = \"super-mario-\\\"64\\\"\" & rating > 80;",
+        );
+    }
+
+    #[test]
+    fn alternative() {
+        let corps = Apicalypse::new()
+            .parmi_l_un(&["developed", "published"], &[1, 2])
+            .egal("parent", 70)
+            .construire();
+
+        assert_eq!(corps, "fields *; where (developed = (1,2) | published = (1,2)) & parent = 70;");
+    }
+
+    #[test]
+    fn tri_croissant() {
+        let corps = Apicalypse::new()
+            .trier("first_release_date", Ordre::Croissant)
+            .construire();
+
+        assert_eq!(corps, "fields *; sort first_release_date asc;");
+    }
+}
@@ -0,0 +1,151 @@
+// Cache disque des réponses IGDB: XDG CACHE/igdb/<endpoint>/<empreinte>.json.
+// Un dossier de fixtures suit la même organisation; il suffit d'y copier le cache.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+use crate::chemin::chemins;
+use crate::donnees::config::{self, ConfigIGDB};
+use crate::interne::empreinte::empreinte;
+use crate::interne::erreurs::TraitErreur;
+
+const SECONDES_PAR_JOUR: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReponseEnregistree {
+    endpoint: String,
+    corps: String,
+    reponse: String,
+    enregistree_le: u64,
+}
+
+pub struct Parametres {
+    pub duree: Duration,
+    pub hors_ligne: bool,
+    pub fixtures: Option<PathBuf>,
+}
+
+// Configuration, que LUDOTHEQUE_HORS_LIGNE et LUDOTHEQUE_FIXTURES_IGDB peuvent
+// surcharger (ex: tests sans identifiants Twitch).
+pub fn parametres() -> Parametres {
+    let ConfigIGDB { duree_cache, hors_ligne, fixtures } = config::obtenir_config().igdb;
+
+    let hors_ligne = match std::env::var("LUDOTHEQUE_HORS_LIGNE") {
+        Ok(valeur) => !matches!(valeur.as_str(), "" | "0" | "non" | "false"),
+        Err(_) => hors_ligne,
+    };
+
+    let fixtures = match std::env::var_os("LUDOTHEQUE_FIXTURES_IGDB") {
+        Some(valeur) => Some(PathBuf::from(valeur)),
+        None => fixtures,
+    };
+
+    Parametres {
+        duree: Duration::from_secs(duree_cache * SECONDES_PAR_JOUR),
+        hors_ligne,
+        fixtures,
+    }
+}
+
+fn maintenant() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duree| duree.as_secs()).unwrap_or(0)
+}
+
+fn nom_fichier(endpoint: &str, corps: &str) -> String {
+    format!("{}/{:016x}.json", endpoint, empreinte(&format!("{}\n{}", endpoint, corps)))
+}
+
+fn lire(chemin: &Path, endpoint: &str, corps: &str) -> Option<ReponseEnregistree> {
+    let contenu = std::fs::read_to_string(chemin).ok()?;
+
+    match serde_json::from_str::<ReponseEnregistree>(&contenu) {
+        // Protège des collisions d'empreinte.
+        Ok(valeur) if valeur.endpoint == endpoint && valeur.corps == corps => Some(valeur),
+        Ok(_) => None,
+        Err(erreur) => {
+            println!("ATTENTION: Réponse IGDB en cache illisible {}: {}", chemin.display(), erreur);
+            None
+        },
+    }
+}
+
+pub fn fixture(dossier: &Path, endpoint: &str, corps: &str) -> Option<String> {
+    lire(&dossier.join(nom_fichier(endpoint, corps)), endpoint, corps).map(|valeur| valeur.reponse)
+}
+
+// Sans limite de durée (None), une réponse expirée reste utilisable, ex: hors ligne.
+pub fn consulter(endpoint: &str, corps: &str, duree: Option<Duration>) -> Option<String> {
+    let chemin = chemins::trouver_chemin(format!("igdb/{}", nom_fichier(endpoint, corps)), chemins::XDG::CACHE).ok()?;
+    let valeur = lire(&chemin, endpoint, corps)?;
+
+    match duree {
+        Some(duree) if maintenant() >= valeur.enregistree_le + duree.as_secs() => None,
+        _ => Some(valeur.reponse),
+    }
+}
+
+pub fn conserver(endpoint: &str, corps: &str, reponse: &str) {
+    let chemin = match chemins::determiner_chemin(format!("igdb/{}", nom_fichier(endpoint, corps)), chemins::XDG::CACHE) {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            println!("ATTENTION: Impossible de conserver la réponse IGDB: {}", erreur.as_string());
+            return;
+        },
+    };
+
+    let valeur = ReponseEnregistree {
+        endpoint: String::from(endpoint),
+        corps: String::from(corps),
+        reponse: String::from(reponse),
+        enregistree_le: maintenant(),
+    };
+
+    let resultat = serde_json::to_string(&valeur)
+        .map_err(|erreur| erreur.to_string())
+        .and_then(|contenu| std::fs::write(&chemin, contenu).map_err(|erreur| erreur.to_string()));
+
+    if let Err(erreur) = resultat {
+        println!("ATTENTION: Impossible d'écrire {}: {}", chemin.display(), erreur);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ecrire_fixture(dossier: &Path, endpoint: &str, corps: &str, reponse: &str) {
+        let chemin = dossier.join(nom_fichier(endpoint, corps));
+        std::fs::create_dir_all(chemin.parent().unwrap()).unwrap();
+
+        let valeur = ReponseEnregistree {
+            endpoint: String::from(endpoint),
+            corps: String::from(corps),
+            reponse: String::from(reponse),
+            enregistree_le: 0,
+        };
+        std::fs::write(chemin, serde_json::to_string(&valeur).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn nom_stable() {
+        assert_eq!(nom_fichier("games", "fields *;"), nom_fichier("games", "fields *;"));
+        assert_ne!(nom_fichier("games", "fields *;"), nom_fichier("platforms", "fields *;"));
+        assert!(nom_fichier("games", "fields *;").starts_with("games/"));
+    }
+
+    #[test]
+    fn fixtures() {
+        let dossier = std::env::temp_dir().join(format!("ludotheque-fixtures-{}", std::process::id()));
+        ecrire_fixture(&dossier, "games", "search \"Zelda\"; fields *; limit 1;", "[{\"id\":1026}]");
+
+        assert_eq!(
+            fixture(&dossier, "games", "search \"Zelda\"; fields *; limit 1;").as_deref(),
+            Some("[{\"id\":1026}]"),
+        );
+        assert_eq!(fixture(&dossier, "games", "search \"Mario\"; fields *; limit 1;"), None);
+
+        let _ = std::fs::remove_dir_all(dossier);
+    }
+}
@@ -14,11 +14,11 @@ impl ErreurClientInaccessible {
 
 impl TraitErreur for ErreurClientInaccessible {
     fn message(&self) -> String {
-        format!("Client IGDB manquant.")
+        String::from("Client IGDB manquant.")
     }
 
     fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.as_string()))
+        Some(self.erreur.as_string())
     }
 }
 
@@ -35,11 +35,11 @@ impl ErreurClientInvalide {
 
 impl TraitErreur for ErreurClientInvalide {
     fn message(&self) -> String {
-        format!("Client IGDB invalide.")
+        String::from("Client IGDB invalide.")
     }
 
     fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.as_string()))
+        Some(self.erreur.as_string())
     }
 }
 
@@ -56,7 +56,7 @@ impl ErreurDemandeToken {
 
 impl TraitErreur for ErreurDemandeToken {
     fn message(&self) -> String {
-        format!("La demande du token IGDB à échouer.")
+        String::from("La demande du token IGDB à échouer.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -77,7 +77,7 @@ impl ErreurRecuperationToken {
 
 impl TraitErreur for ErreurRecuperationToken {
     fn message(&self) -> String {
-        format!("Impossible de récupérer le token IGDB.")
+        String::from("Impossible de récupérer le token IGDB.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -90,19 +90,13 @@ pub struct ErreurEnregistrementExpire {
     pub erreur: json::err::Erreur,
 }
 
-impl ErreurEnregistrementExpire {
-    pub fn as_err<T>(self) -> Result<T, Erreur> {
-        Err(Erreur::ErreurEnregistrementExpire(self))
-    }
-}
-
 impl TraitErreur for ErreurEnregistrementExpire {
     fn message(&self) -> String {
-        format!("Impossible d'enregistrer l'expiration du token IGDB.")
+        String::from("Impossible d'enregistrer l'expiration du token IGDB.")
     }
 
     fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.as_string()))
+        Some(self.erreur.as_string())
     }
 }
 
@@ -119,7 +113,7 @@ impl ErreurConstructionRequete {
 
 impl TraitErreur for ErreurConstructionRequete {
     fn message(&self) -> String {
-        format!("Impossible de contruire la requête IGDB.")
+        String::from("Impossible de contruire la requête IGDB.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -140,7 +134,7 @@ impl ErreurDemandeRequete {
 
 impl TraitErreur for ErreurDemandeRequete {
     fn message(&self) -> String {
-        format!("La requete IGDB a échoué.")
+        String::from("La requete IGDB a échoué.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -170,6 +164,74 @@ impl TraitErreur for ErreurTraitementRequete {
     }
 }
 
+// Erreur StatutRequete
+pub struct ErreurStatutRequete {
+    pub statut: reqwest::StatusCode,
+    pub reponse: String,
+}
+
+impl ErreurStatutRequete {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurStatutRequete(self))
+    }
+}
+
+impl TraitErreur for ErreurStatutRequete {
+    fn message(&self) -> String {
+        match self.statut {
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN =>
+                format!("IGDB a refusé le token, même renouvelé ({}). Vérifiez igdb.json.", self.statut),
+            statut => format!("IGDB a répondu {}.", statut),
+        }
+    }
+
+    fn cause(&self) -> Option<String> {
+        match self.reponse.is_empty() {
+            true => None,
+            false => Some(self.reponse.clone()),
+        }
+    }
+}
+
+// Erreur MultiqueryIncomplete
+pub struct ErreurMultiqueryIncomplete {
+    pub attendues: usize,
+    pub recues: usize,
+}
+
+impl ErreurMultiqueryIncomplete {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurMultiqueryIncomplete(self))
+    }
+}
+
+impl TraitErreur for ErreurMultiqueryIncomplete {
+    fn message(&self) -> String {
+        format!("Réponse multiquery incomplète: {} résultat(s) sur {}.", self.recues, self.attendues)
+    }
+}
+
+// Erreur HorsLigne
+pub struct ErreurHorsLigne {
+    pub endpoint: String,
+    pub corps: String,
+}
+
+impl ErreurHorsLigne {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurHorsLigne(self))
+    }
+}
+
+impl TraitErreur for ErreurHorsLigne {
+    fn message(&self) -> String {
+        format!("Mode hors ligne: aucune réponse IGDB en cache pour {}.", self.endpoint)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.corps.clone())
+    }
+}
 
 // Enum Erreur
 pub enum Erreur{
@@ -177,10 +239,12 @@ pub enum Erreur{
     ClientInvalide(ErreurClientInvalide),
     ErreurDemandeToken(ErreurDemandeToken),
     ErreurRecuperationToken(ErreurRecuperationToken),
-    ErreurEnregistrementExpire(ErreurEnregistrementExpire),
     ErreurConstructionRequete(ErreurConstructionRequete),
     ErreurDemandeRequete(ErreurDemandeRequete),
     ErreurTraitementRequete(ErreurTraitementRequete),
+    ErreurStatutRequete(ErreurStatutRequete),
+    ErreurMultiqueryIncomplete(ErreurMultiqueryIncomplete),
+    ErreurHorsLigne(ErreurHorsLigne),
 }
 
 impl Erreur {
@@ -190,10 +254,12 @@ impl Erreur {
             Erreur::ClientInvalide(erreur) => erreur,
             Erreur::ErreurDemandeToken(erreur) => erreur,
             Erreur::ErreurRecuperationToken(erreur) => erreur,
-            Erreur::ErreurEnregistrementExpire(erreur) => erreur,
             Erreur::ErreurConstructionRequete(erreur) => erreur,
             Erreur::ErreurDemandeRequete(erreur) => erreur,
             Erreur::ErreurTraitementRequete(erreur) => erreur,
+            Erreur::ErreurStatutRequete(erreur) => erreur,
+            Erreur::ErreurMultiqueryIncomplete(erreur) => erreur,
+            Erreur::ErreurHorsLigne(erreur) => erreur,
         }
     }
 }
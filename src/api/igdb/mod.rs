@@ -1,15 +1,22 @@
+pub mod apicalypse;
+pub mod cache;
 pub mod err;
 pub mod requete;
 
+use crate::api::igdb::apicalypse::Apicalypse;
 use crate::api::igdb::err::*;
 use crate::api::igdb::requete::*;
+use crate::api::client_http;
 use crate::chemin::{json, chemins};
+use crate::donnees::config::{self, ConfigServeur};
 use crate::interne::erreurs::TraitErreur;
 
 //use async_std::task;
+use lazy_static::lazy_static;
 use serde::de::DeserializeOwned;
 use serde::{Serialize, Deserialize};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, OnceCell, Semaphore};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Client {
@@ -31,21 +38,21 @@ struct Expire {
 
 fn client_modele() -> Client {
     Client {
-        client_id: format!("your_client_id"),
-        client_secret: format!("your_client_secret"),
+        client_id: String::from("your_client_id"),
+        client_secret: String::from("your_client_secret"),
     }
 }
 
 fn charger_client() -> Result<Client, json::err::Erreur> {
-   json::charger_json_xdg(format!("igdb.json"), chemins::XDG::CONFIG)
+   json::charger_json_xdg(String::from("igdb.json"), chemins::XDG::CONFIG)
 }
 
 fn enregistrer_client(client: Client) -> Result<Client, json::err::Erreur> {
-    json::enregistrer_json_xdg(client, format!("igdb.json"), chemins::XDG::CONFIG)
+    json::enregistrer_json_xdg(client, String::from("igdb.json"), chemins::XDG::CONFIG)
 }
 
 fn charger_token() -> Result<Token, json::err::Erreur> {
-    json::charger_json_xdg(format!("token.json"), chemins::XDG::CACHE)
+    json::charger_json_xdg(String::from("token.json"), chemins::XDG::CACHE)
 }
 
 fn obtenir_client() -> Result<Client, Erreur> {
@@ -93,14 +100,13 @@ async fn demander_token() -> Result<String, Erreur> {
     let client: Client = obtenir_client()?;
 
     let url = format!(
-        "https://id.twitch.tv/oauth2/token?client_id={}&client_secret={}&grant_type=client_credentials",
+        "{}?client_id={}&client_secret={}&grant_type=client_credentials",
+        config::obtenir_config().serveurs.twitch.url("oauth2/token"),
         client.client_id,
         client.client_secret,
     );
 
-    let webclient = reqwest::Client::new();
-
-    let reponse = match webclient.post(url).send().await {
+    let reponse = match CLIENT_TWITCH.post(url).send().await {
         Ok(resultat) => resultat.text().await,
         Err(erreur) => return ErreurDemandeToken { erreur }.as_err(),
     };
@@ -154,11 +160,7 @@ fn verifier_token() -> bool {
         Err(_) => return false,
     };
 
-    if maintenant > expiration {
-        false
-    } else {
-        true
-    }
+    maintenant <= expiration
 }
 
 async fn obtenir_token() -> Result<Token, Erreur> {
@@ -172,28 +174,94 @@ async fn obtenir_token() -> Result<Token, Erreur> {
     }
 }
 
+// IGDB accepte 4 requêtes par seconde et 8 requêtes simultanées.
+const INTERVALLE_REQUETES: Duration = Duration::from_millis(250);
+const REQUETES_SIMULTANEES: usize = 8;
+const TENTATIVES: u32 = 4;
+const ATTENTE_INITIALE: u64 = 500;
+
+lazy_static! {
+    static ref CLIENT_HTTP: reqwest::Client = client_http(&config::obtenir_config().serveurs.igdb);
+    static ref CLIENT_TWITCH: reqwest::Client = client_http(&config::obtenir_config().serveurs.twitch);
+    static ref CLIENT_IGDB: OnceCell<ClientIGDB> = OnceCell::new();
+    static ref SIMULTANEES: Semaphore = Semaphore::new(REQUETES_SIMULTANEES);
+    static ref PROCHAINE_REQUETE: Mutex<Instant> = Mutex::new(Instant::now());
+}
+
+// Attend le prochain créneau libre pour respecter le débit d'IGDB.
+async fn attendre_creneau() {
+    let attente = {
+        let mut prochaine = PROCHAINE_REQUETE.lock().await;
+        let maintenant = Instant::now();
+        let creneau = std::cmp::max(*prochaine, maintenant);
+        *prochaine = creneau + INTERVALLE_REQUETES;
+        creneau - maintenant
+    };
+
+    if !attente.is_zero() {
+        tokio::time::sleep(attente).await;
+    }
+}
+
+// Token courant et numéro de renouvellement: une requête refusée ne renouvelle le
+// token que s'il n'a pas changé depuis son envoi.
+#[derive(Clone)]
+struct Jeton {
+    generation: u64,
+    valeur: String,
+}
+
 pub struct ClientIGDB {
     client_id: String,
-    access_token: String,
+    access_token: Mutex<Jeton>,
+    cache: cache::Parametres,
+    serveur: ConfigServeur,
 }
 
 impl ClientIGDB {
     pub async fn new() -> Result<ClientIGDB, Erreur> {
+        let cache = cache::parametres();
+        let serveur = config::obtenir_config().serveurs.igdb;
+
+        // Hors ligne, ni identifiants ni token: tout vient du cache ou des fixtures.
+        if cache.hors_ligne {
+            println!("INFO: IGDB hors ligne; seules les réponses en cache sont utilisées.");
+            return Ok(ClientIGDB { client_id: String::new(), access_token: Mutex::new(Jeton { generation: 0, valeur: String::new() }), cache, serveur });
+        }
+
         let client_id = obtenir_client()?.client_id;
         let access_token = obtenir_token().await?.access_token;
-        Ok(ClientIGDB { client_id, access_token })
+        Ok(ClientIGDB { client_id, access_token: Mutex::new(Jeton { generation: 0, valeur: access_token }), cache, serveur })
     }
 
-    pub async fn demander(&self, endpoint: String, corps: String) -> Result<String, Erreur> {
-        let url = format!("https://api.igdb.com/v4/{}", endpoint);
+    // Client partagé par toute l'application.
+    pub async fn partage() -> Result<&'static ClientIGDB, Erreur> {
+        CLIENT_IGDB.get_or_try_init(ClientIGDB::new).await
+    }
 
-        let client = reqwest::Client::new();
+    // Le verrou est gardé pendant la demande: les requêtes refusées en même temps
+    // attendent le même renouvellement au lieu d'en demander chacune un.
+    async fn renouveler_token(&self, generation: u64) -> Result<(), Erreur> {
+        let mut jeton = self.access_token.lock().await;
 
+        if jeton.generation != generation {
+            return Ok(());
+        }
+
+        println!("INFO: Token IGDB refusé; demande d'un nouveau token.");
+        jeton.valeur = recuperer_token().await?.access_token;
+        jeton.generation += 1;
+
+        Ok(())
+    }
+
+    // Le corps est lu avant de rendre la place: la limite porte sur les requêtes entières.
+    async fn envoyer(&self, url: &str, corps: &str, jeton: &Jeton) -> Result<(reqwest::StatusCode, String), Erreur> {
         let client_id = match reqwest::header::HeaderValue::from_str(self.client_id.as_str()) {
             Ok(valeur) => valeur,
             Err(erreur) => return ErreurConstructionRequete { erreur }.as_err(),
         };
-        let access_token = match reqwest::header::HeaderValue::from_str(format!("Bearer {}", self.access_token).as_str()) {
+        let access_token = match reqwest::header::HeaderValue::from_str(format!("Bearer {}", jeton.valeur).as_str()) {
             Ok(valeur) => valeur,
             Err(erreur) => return ErreurConstructionRequete { erreur }.as_err(),
         };
@@ -203,22 +271,95 @@ impl ClientIGDB {
         entete.insert("Authorization", access_token);
         entete.insert("Accept", reqwest::header::HeaderValue::from_static("application/json"));
 
-        let reponse = match client
+        let _permis = SIMULTANEES.acquire().await;
+        attendre_creneau().await;
+
+        let reponse = match CLIENT_HTTP
             .post(url)
             .headers(entete)
-            .body(corps)
+            .body(corps.to_string())
             .send()
             .await {
-            Ok(resultat) => resultat.text().await,
+            Ok(valeur) => valeur,
             Err(erreur) => return ErreurDemandeRequete { erreur }.as_err(),
         };
 
-        match reponse {
-            Ok(valeur) => Ok(valeur),
+        let statut = reponse.status();
+
+        match reponse.text().await {
+            Ok(texte) => Ok((statut, texte)),
             Err(erreur) => ErreurDemandeRequete { erreur }.as_err(),
         }
     }
 
+    pub async fn demander(&self, endpoint: String, corps: String) -> Result<String, Erreur> {
+        if let Some(reponse) = self.cache.fixtures.as_ref().and_then(|dossier| cache::fixture(dossier, &endpoint, &corps)) {
+            return Ok(reponse);
+        }
+
+        let duree = match self.cache.hors_ligne {
+            true => None,
+            false => Some(self.cache.duree),
+        };
+
+        if let Some(reponse) = cache::consulter(&endpoint, &corps, duree) {
+            return Ok(reponse);
+        }
+
+        if self.cache.hors_ligne {
+            return ErreurHorsLigne { endpoint, corps }.as_err();
+        }
+
+        let reponse = self.interroger(&endpoint, &corps).await?;
+
+        if !self.cache.duree.is_zero() {
+            cache::conserver(&endpoint, &corps, &reponse);
+        }
+
+        Ok(reponse)
+    }
+
+    async fn interroger(&self, endpoint: &str, corps: &str) -> Result<String, Erreur> {
+        let url = self.serveur.url(endpoint);
+
+        let mut tentative = 1;
+        let mut token_renouvele = false;
+
+        loop {
+            let jeton = self.access_token.lock().await.clone();
+            let resultat = self.envoyer(&url, corps, &jeton).await;
+
+            let temporaire = match &resultat {
+                Ok((statut, _)) => *statut == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    || statut.is_server_error(),
+                Err(Erreur::ErreurDemandeRequete(_)) => true,
+                Err(_) => false,
+            };
+
+            if temporaire && tentative < TENTATIVES {
+                let attente = ATTENTE_INITIALE * 2u64.pow(tentative - 1);
+                println!("INFO: Requête IGDB en échec; nouvelle tentative dans {} ms.", attente);
+                tokio::time::sleep(Duration::from_millis(attente)).await;
+                tentative += 1;
+                continue;
+            }
+
+            let (statut, texte) = resultat?;
+
+            if statut == reqwest::StatusCode::UNAUTHORIZED && !token_renouvele {
+                self.renouveler_token(jeton.generation).await?;
+                token_renouvele = true;
+                continue;
+            }
+
+            if !statut.is_success() {
+                return ErreurStatutRequete { statut, reponse: texte }.as_err();
+            }
+
+            return Ok(texte);
+        }
+    }
+
     pub async fn solliciter<T: DeserializeOwned>(&self, requete: Requete<T>) -> Result<T, Erreur> {
         let reponse = self.demander(requete.endpoint, requete.corps).await?;
 
@@ -229,5 +370,69 @@ impl ClientIGDB {
 
         Ok(resultat)
     }
-}
 
+    // Regroupe les requêtes par 10 dans des multiquery; les résultats gardent l'ordre des requêtes.
+    pub async fn solliciter_plusieurs<T: DeserializeOwned>(&self, requetes: Vec<Requete<T>>) -> Result<Vec<T>, Erreur> {
+        let mut resultats: Vec<T> = vec![];
+
+        for lot in requetes.chunks(REQUETES_PAR_MULTIQUERY) {
+            let mut ordonnes: Vec<Option<T>> = lot.iter().map(|_| None).collect();
+
+            for reponse in self.solliciter(requete_multiple(lot)).await? {
+                match indice_sous_requete(&reponse.name) {
+                    Some(indice) if indice < ordonnes.len() => ordonnes[indice] = Some(reponse.result),
+                    _ => println!("ATTENTION: Sous-requête IGDB inattendue: {}.", reponse.name),
+                }
+            }
+
+            let recues = ordonnes.iter().filter(|resultat| resultat.is_some()).count();
+            if recues < lot.len() {
+                return ErreurMultiqueryIncomplete { attendues: lot.len(), recues }.as_err();
+            }
+
+            resultats.extend(ordonnes.into_iter().flatten());
+        }
+
+        Ok(resultats)
+    }
+
+    // Récupère des objets par identifiant, par lots de 500 (where id = (1,2,3)).
+    pub async fn solliciter_par_lots<T, F>(&self, ids: &[u32], construire: F) -> Result<Vec<T>, Erreur>
+    where
+        T: DeserializeOwned,
+        F: Fn(&[u32]) -> Requete<Vec<T>>,
+    {
+        let mut requetes: Vec<Requete<Vec<T>>> = ids.chunks(TAILLE_PAGE as usize)
+            .map(construire)
+            .collect();
+
+        let lots = match requetes.len() {
+            0 => vec![],
+            1 => vec![self.solliciter(requetes.remove(0)).await?],
+            _ => self.solliciter_plusieurs(requetes).await?,
+        };
+
+        Ok(lots.into_iter().flatten().collect())
+    }
+
+    // Parcourt tous les résultats d'une requête, page par page.
+    pub async fn paginer<T: DeserializeOwned>(&self, endpoint: Endpoint, corps: Apicalypse) -> Result<Vec<T>, Erreur> {
+        let mut resultats: Vec<T> = vec![];
+        let mut decalage = 0;
+
+        loop {
+            let page: Vec<T> = self.solliciter(
+                Requete::new(endpoint, corps.clone().limiter(TAILLE_PAGE).decaler(decalage))
+            ).await?;
+
+            let derniere = (page.len() as u32) < TAILLE_PAGE;
+            resultats.extend(page);
+
+            if derniere {
+                return Ok(resultats);
+            }
+
+            decalage += TAILLE_PAGE;
+        }
+    }
+}
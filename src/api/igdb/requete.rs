@@ -1,53 +1,247 @@
 //use serde::{Deserialize, de::DeserializeOwned};
+use std::marker::PhantomData;
 
+use serde::Deserialize;
+
+use crate::api::igdb::apicalypse::{Apicalypse, Ordre};
 use crate::donnees::objet;
 
+// Limites imposées par IGDB: 500 résultats par requête, 10 requêtes par multiquery.
+pub const TAILLE_PAGE: u32 = 500;
+pub const REQUETES_PAR_MULTIQUERY: usize = 10;
+
+const CHAMPS_JEU: &[&str] = &[
+    "name", "slug", "storyline", "summary", "first_release_date", "category", "platforms",
+    "remakes", "remasters", "similar_games", "rating", "rating_count", "updated_at",
+];
+
+const DEVELOPPEMENTS_JEU: &[&str] = &[
+    "alternative_names", "collection", "franchise", "genres", "themes", "keywords",
+    "cover", "artworks", "screenshots", "videos",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endpoint {
+    Jeux,
+    Plateformes,
+    Entreprises,
+    Multiple,
+}
+
+impl Endpoint {
+    pub fn chemin(&self) -> &'static str {
+        match self {
+            Endpoint::Jeux => "games",
+            Endpoint::Plateformes => "platforms",
+            Endpoint::Entreprises => "companies",
+            Endpoint::Multiple => "multiquery",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Requete<T> {
     pub endpoint: String,
     pub corps: String,
-    resultat: Option<T>,
+    // Type de la réponse attendue.
+    resultat: PhantomData<T>,
+}
+
+impl<T> Requete<T> {
+    pub fn new(endpoint: Endpoint, corps: Apicalypse) -> Self {
+        Requete {
+            endpoint: String::from(endpoint.chemin()),
+            corps: corps.construire(),
+            resultat: PhantomData,
+        }
+    }
+
+    // Forme nommée de la requête, à placer dans un multiquery.
+    fn sous_requete(&self, nom: &str) -> String {
+        format!("query {} \"{}\" {{ {} }};", self.endpoint, nom, self.corps)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResultatMultiple<T> {
+    pub name: String,
+    pub result: T,
+}
+
+// Les sous-requêtes sont nommées r0, r1… pour retrouver leur ordre dans la réponse.
+pub fn indice_sous_requete(nom: &str) -> Option<usize> {
+    nom.strip_prefix('r')?.parse().ok()
+}
+
+pub fn requete_multiple<T>(requetes: &[Requete<T>]) -> Requete<Vec<ResultatMultiple<T>>> {
+    let corps: Vec<String> = requetes.iter()
+        .enumerate()
+        .map(|(indice, requete)| requete.sous_requete(&format!("r{}", indice)))
+        .collect();
+
+    Requete {
+        endpoint: String::from(Endpoint::Multiple.chemin()),
+        corps: corps.join(" "),
+        resultat: PhantomData,
+    }
+}
+
+fn champs_jeu() -> Apicalypse {
+    Apicalypse::new()
+        .champs(CHAMPS_JEU)
+        .developper(DEVELOPPEMENTS_JEU)
 }
 
 pub fn requete_trouver_jeu(
     nom: String,
     plateforme_id: Option<u32>
 ) -> Requete<Vec<objet::JeuIGDB>> {
+    let mut corps = champs_jeu().rechercher(&nom);
 
-    Requete {
-        endpoint: format!("games/"),
-        corps: format!("{}{}{}{}",
-            format!("search \"{}\";", nom),
-            concat!(
-                "fields name, slug, alternative_names.*, storyline, summary, first_release_date, ",
-                "collection.*, franchise.*, category, genres.*, themes.*, keywords.*, platforms, ",
-                "remakes, remasters, similar_games, rating, rating_count, cover.*, artworks.*, ",
-                "screenshots.*, videos.*, updated_at;"
-            ),
-            "limit 1;",
-            match plateforme_id {
-                Some(id) => format!("where platforms = ({})", id),
-                None => format!(""),
-            }
-        ),
-        resultat: None,
+    if let Some(id) = plateforme_id {
+        corps = corps.parmi("platforms", &[id]);
     }
 
+    Requete::new(Endpoint::Jeux, corps.limiter(1))
 }
 
+#[allow(dead_code)]
 pub fn requete_recuperer_jeu(id: u32) -> Requete<Vec<objet::JeuIGDB>> {
-    Requete {
-        endpoint: format!("games/"),
-        corps: format!("{}{}{}",
-            format!("where id = {};", id),
+    Requete::new(Endpoint::Jeux, champs_jeu().egal("id", id).limiter(1))
+}
+
+pub fn requete_recuperer_jeux(ids: &[u32]) -> Requete<Vec<objet::JeuIGDB>> {
+    Requete::new(Endpoint::Jeux, champs_jeu().parmi("id", ids).limiter(ids.len() as u32))
+}
+
+fn champs_plateforme() -> Apicalypse {
+    Apicalypse::new()
+        .champs(&["name", "slug", "summary", "category", "updated_at"])
+        .developper(&["platform_logo"])
+}
+
+fn champs_entreprise() -> Apicalypse {
+    Apicalypse::new()
+        .champs(&["name", "slug", "description", "developed", "published", "parent", "start_date", "updated_at"])
+        .developper(&["logo"])
+}
+
+pub fn requete_recuperer_plateformes(ids: &[u32]) -> Requete<Vec<objet::PlateformeIGDB>> {
+    Requete::new(
+        Endpoint::Plateformes,
+        champs_plateforme().parmi("id", ids).limiter(ids.len() as u32),
+    )
+}
+
+#[allow(dead_code)]
+pub fn requete_recuperer_entreprises(ids: &[u32]) -> Requete<Vec<objet::EntrepriseIGDB>> {
+    Requete::new(
+        Endpoint::Entreprises,
+        champs_entreprise().parmi("id", ids).limiter(ids.len() as u32),
+    )
+}
+
+// Corps à paginer: entreprises ayant développé ou édité l'un des jeux.
+pub fn corps_entreprises_jeux(jeux: &[u32]) -> Apicalypse {
+    champs_entreprise()
+        .parmi_l_un(&["developed", "published"], jeux)
+        .trier("id", Ordre::Croissant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAMPS: &str = concat!(
+        "fields name,slug,storyline,summary,first_release_date,category,platforms,",
+        "remakes,remasters,similar_games,rating,rating_count,updated_at,",
+        "alternative_names.*,collection.*,franchise.*,genres.*,themes.*,keywords.*,",
+        "cover.*,artworks.*,screenshots.*,videos.*;",
+    );
+
+    #[test]
+    fn trouver_jeu() {
+        let requete = requete_trouver_jeu(String::from("Pokémon \"Rouge\""), None);
+
+        assert_eq!(requete.endpoint, "games");
+        assert_eq!(requete.corps, format!("search \"Pokémon \\\"Rouge\\\"\"; {} limit 1;", CHAMPS));
+    }
+
+    #[test]
+    fn trouver_jeu_sur_plateforme() {
+        let requete = requete_trouver_jeu(String::from("Zelda"), Some(19));
+
+        assert_eq!(requete.corps, format!("search \"Zelda\"; {} where platforms = (19); limit 1;", CHAMPS));
+    }
+
+    #[test]
+    fn recuperer_jeu() {
+        let requete = requete_recuperer_jeu(1026);
+
+        assert_eq!(requete.endpoint, "games");
+        assert_eq!(requete.corps, format!("{} where id = 1026; limit 1;", CHAMPS));
+    }
+
+    #[test]
+    fn recuperer_jeux() {
+        let requete = requete_recuperer_jeux(&[1, 2, 3]);
+
+        assert_eq!(requete.corps, format!("{} where id = (1,2,3); limit 3;", CHAMPS));
+    }
+
+    #[test]
+    fn recuperer_plateformes() {
+        let requete = requete_recuperer_plateformes(&[18, 19]);
+
+        assert_eq!(requete.endpoint, "platforms");
+        assert_eq!(
+            requete.corps,
+            "fields name,slug,summary,category,updated_at,platform_logo.*; where id = (18,19); limit 2;",
+        );
+    }
+
+    #[test]
+    fn recuperer_entreprises() {
+        let requete = requete_recuperer_entreprises(&[70]);
+
+        assert_eq!(requete.endpoint, "companies");
+        assert_eq!(
+            requete.corps,
+            "fields name,slug,description,developed,published,parent,start_date,updated_at,logo.*; where id = (70); limit 1;",
+        );
+    }
+
+    #[test]
+    fn entreprises_jeux() {
+        assert_eq!(
+            corps_entreprises_jeux(&[1026, 1027]).construire(),
+            concat!(
+                "fields name,slug,description,developed,published,parent,start_date,updated_at,logo.*; ",
+                "where (developed = (1026,1027) | published = (1026,1027)); sort id asc;",
+            ),
+        );
+    }
+
+    #[test]
+    fn multiple() {
+        let requete = requete_multiple(&[
+            requete_recuperer_plateformes(&[18]),
+            requete_recuperer_plateformes(&[19]),
+        ]);
+
+        assert_eq!(requete.endpoint, "multiquery");
+        assert_eq!(
+            requete.corps,
             concat!(
-                "fields name, slug, alternative_names.*, storyline, summary, first_release_date, ",
-                "collection.*, franchise.*, category, genres.*, themes.*, keywords.*, platforms, ",
-                "remakes, remasters, similar_games, rating, rating_count, cover.*, artworks.*, ",
-                "screenshots.*, videos.*, updated_at;"
+                "query platforms \"r0\" { fields name,slug,summary,category,updated_at,platform_logo.*; where id = (18); limit 1; }; ",
+                "query platforms \"r1\" { fields name,slug,summary,category,updated_at,platform_logo.*; where id = (19); limit 1; };",
             ),
-            "limit 1;",
-        ),
-        resultat: None,
+        );
+    }
+
+    #[test]
+    fn indices() {
+        assert_eq!(indice_sous_requete("r12"), Some(12));
+        assert_eq!(indice_sous_requete("jeux"), None);
     }
 }
@@ -1,2 +1,30 @@
 pub mod igdb;
 pub mod openai;
+
+use std::time::Duration;
+
+use crate::donnees::config::ConfigServeur;
+
+// Client HTTP selon la configuration du serveur (délai, proxy).
+pub fn client_http(serveur: &ConfigServeur) -> reqwest::Client {
+    let mut constructeur = reqwest::Client::builder();
+
+    if serveur.delai > 0 {
+        constructeur = constructeur.timeout(Duration::from_secs(serveur.delai));
+    }
+
+    if let Some(proxy) = &serveur.proxy {
+        match reqwest::Proxy::all(proxy.as_str()) {
+            Ok(valeur) => constructeur = constructeur.proxy(valeur),
+            Err(erreur) => println!("ATTENTION: Proxy {} ignoré: {}", proxy, erreur),
+        }
+    }
+
+    match constructeur.build() {
+        Ok(client) => client,
+        Err(erreur) => {
+            println!("ATTENTION: Client HTTP pour {} par défaut: {}", serveur.url, erreur);
+            reqwest::Client::new()
+        },
+    }
+}
@@ -14,11 +14,11 @@ impl ErreurTokenInaccessible {
 
 impl TraitErreur for ErreurTokenInaccessible {
     fn message(&self) -> String {
-        format!("Token OpenAI manquant.")
+        String::from("Token OpenAI manquant.")
     }
 
     fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.as_string()))
+        Some(self.erreur.as_string())
     }
 }
 
@@ -35,11 +35,11 @@ impl ErreurTokenInvalide {
 
 impl TraitErreur for ErreurTokenInvalide {
     fn message(&self) -> String {
-        format!("Token OpenAI invalide.")
+        String::from("Token OpenAI invalide.")
     }
 
     fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.as_string()))
+        Some(self.erreur.as_string())
     }
 }
 
@@ -56,7 +56,7 @@ impl ErreurConstructionRequete {
 
 impl TraitErreur for ErreurConstructionRequete {
     fn message(&self) -> String {
-        format!("Impossible de contruire la requête OpenAI.")
+        String::from("Impossible de contruire la requête OpenAI.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -77,7 +77,7 @@ impl ErreurDemandeRequete {
 
 impl TraitErreur for ErreurDemandeRequete {
     fn message(&self) -> String {
-        format!("La requête OpenAI a échoué.")
+        String::from("La requête OpenAI a échoué.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -4,7 +4,9 @@ pub mod objet;
 
 use crate::api::openai::err::*;
 use crate::api::openai::requete::*;
+use crate::api::client_http;
 use crate::chemin::{json, chemins};
+use crate::donnees::config::{self, ConfigServeur};
 
 use serde::de::DeserializeOwned;
 use serde::{Serialize, Deserialize};
@@ -16,12 +18,12 @@ struct Token {
 
 fn token_modele() -> Token {
     Token {
-        secret_key: format!("your_openai_api_key"),
+        secret_key: String::from("your_openai_api_key"),
     }
 }
 
 fn charger_token() -> Result<Token, json::err::Erreur> {
-    json::charger_json_xdg(format!("openai.json"), chemins::XDG::CONFIG)
+    json::charger_json_xdg(String::from("openai.json"), chemins::XDG::CONFIG)
 }
 
 fn obtenir_token() -> Result<Token, Erreur> {
@@ -48,18 +50,20 @@ fn enregistrer_token(token: Token) -> Result<Token, json::err::Erreur> {
 
 pub struct ClientOpenAI {
     secret_key: String,
+    serveur: ConfigServeur,
+    client: reqwest::Client,
 }
 
 impl ClientOpenAI {
     pub async fn new() -> Result<ClientOpenAI, Erreur> {
         let secret_key = obtenir_token()?.secret_key;
-        Ok(ClientOpenAI { secret_key })
+        let serveur = config::obtenir_config().serveurs.openai;
+        let client = client_http(&serveur);
+        Ok(ClientOpenAI { secret_key, serveur, client })
     }
 
     pub async fn demander(&self, corps: String) -> Result<String, Erreur> {
-        let url = format!("https://api.openai.com/v1/chat/completions");
-
-        let client = reqwest::Client::new();
+        let url = self.serveur.url("chat/completions");
 
         let secret_key = match reqwest::header::HeaderValue::from_str(format!("Bearer {}", self.secret_key).as_str()) {
             Ok(valeur) => valeur,
@@ -70,7 +74,7 @@ impl ClientOpenAI {
         entete.insert("Authorization", secret_key);
         entete.insert("Content-Type", reqwest::header::HeaderValue::from_static("application/json"));
 
-        let reponse = match client
+        let reponse = match self.client
             .post(url)
             .headers(entete)
             .body(corps)
@@ -1,3 +1,6 @@
+// Réponse de l'API telle que documentée; tous les champs ne sont pas lus.
+#![allow(dead_code)]
+
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
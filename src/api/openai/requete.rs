@@ -1,5 +1,7 @@
 //use serde::{Deserialize, de::DeserializeOwned};
 
+use std::marker::PhantomData;
+
 use crate::api::openai::objet;
 
 fn obtenir_modele() -> String {
@@ -13,7 +15,8 @@ fn obtenir_temperature() -> f32 {
 #[derive(Debug)]
 pub struct Requete<T> {
     pub corps: String,
-    resultat: Option<T>,
+    // Type de la réponse attendue.
+    resultat: PhantomData<T>,
 }
 
 pub fn requete(
@@ -36,7 +39,7 @@ pub fn requete(
             utilisateur,
             obtenir_temperature(),
         ),
-        resultat: None,
+        resultat: PhantomData,
     }
 
 }
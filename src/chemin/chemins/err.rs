@@ -14,7 +14,7 @@ impl ErreurXDG {
 
 impl TraitErreur for ErreurXDG {
     fn message(&self) -> String {
-        format!("Impossible de trouver les répertoires XDG.")
+        String::from("Impossible de trouver les répertoires XDG.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -11,13 +11,12 @@ pub enum XDG {
     CACHE,
     CONFIG,
     DATA,
-    STATE,
 }
 
 fn obtenir_repertoires_xdg() -> Result<xdg::BaseDirectories, Erreur> {
     match xdg::BaseDirectories::with_prefix(PREFIX_XDG) {
         Ok(valeur) => Ok(valeur),
-        Err(erreur) => Err(Erreur::ErreurXDG(ErreurXDG { erreur })),
+        Err(erreur) => ErreurXDG { erreur }.as_err(),
     }
 }
 
@@ -28,7 +27,6 @@ pub fn determiner_chemin(nom: String, xdg_type: XDG) -> Result<PathBuf, Erreur>
         XDG::CACHE => xdg_dirs.place_cache_file(nom.clone()),
         XDG::CONFIG => xdg_dirs.place_config_file(nom.clone()),
         XDG::DATA => xdg_dirs.place_data_file(nom.clone()),
-        XDG::STATE => xdg_dirs.place_state_file(nom.clone()),
     };
 
     let chemin = match place_fichier {
@@ -36,7 +34,7 @@ pub fn determiner_chemin(nom: String, xdg_type: XDG) -> Result<PathBuf, Erreur>
         Err(erreur) => return ErreurRepertoireXDG { xdg_type, erreur }.as_err(),
     };
 
-    Ok(PathBuf::from(chemin))
+    Ok(chemin)
 }
 
 pub fn trouver_chemin(nom: String, xdg_type: XDG) -> Result<PathBuf, Erreur> {
@@ -46,7 +44,6 @@ pub fn trouver_chemin(nom: String, xdg_type: XDG) -> Result<PathBuf, Erreur> {
         XDG::CACHE => xdg_dirs.find_cache_file(nom.clone()),
         XDG::CONFIG => xdg_dirs.find_config_file(nom.clone()),
         XDG::DATA => xdg_dirs.find_data_file(nom.clone()),
-        XDG::STATE => xdg_dirs.find_state_file(nom.clone()),
     };
 
     let chemin = match trouve_fichier {
@@ -54,6 +51,6 @@ pub fn trouver_chemin(nom: String, xdg_type: XDG) -> Result<PathBuf, Erreur> {
         None => return ErreurFichierIntrouvable { xdg_type, nom }.as_err(),
     };
 
-    Ok(PathBuf::from(chemin))
+    Ok(chemin)
 }
 
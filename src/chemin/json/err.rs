@@ -87,7 +87,7 @@ impl TraitErreur for ErreurEnregistrementImpossible {
     }
 
     fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.as_string()))
+        Some(self.erreur.as_string())
     }
 }
 
@@ -109,7 +109,7 @@ impl TraitErreur for ErreurChargementImpossible {
     }
 
     fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.as_string()))
+        Some(self.erreur.as_string())
     }
 }
 
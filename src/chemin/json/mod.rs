@@ -11,23 +11,17 @@ use std::io::prelude::*;
 pub fn enregistrer_json<T: Serialize>(json: T, chemin_json: PathBuf) -> Result<T, Erreur> {
     let json_serialise = match serde_json::to_string(&json) {
         Ok(valeur) => valeur,
-        Err(erreur) => return Err(
-            Erreur::ErreurSerialisation(ErreurSerialisation { fichier: chemin_json, erreur })
-        ),
+        Err(erreur) => return ErreurSerialisation { fichier: chemin_json, erreur }.as_err(),
     };
 
     let mut fichier_json = match File::create(chemin_json.as_path()) {
         Ok(valeur) => valeur,
-        Err(erreur) => return Err(
-            Erreur::ErreurPermission(ErreurPermission { fichier: chemin_json, erreur })
-        ),
+        Err(erreur) => return ErreurPermission { fichier: chemin_json, erreur }.as_err(),
     };
 
     match fichier_json.write_all(json_serialise.as_bytes()) {
         Ok(_) => {},
-        Err(erreur) => return Err(
-            Erreur::ErreurPermission(ErreurPermission { fichier: chemin_json, erreur })
-        ),
+        Err(erreur) => return ErreurPermission { fichier: chemin_json, erreur }.as_err(),
     }
 
     println!("INFO: {:?} enregistré.", &chemin_json);
@@ -38,36 +32,26 @@ pub fn enregistrer_json<T: Serialize>(json: T, chemin_json: PathBuf) -> Result<T
 pub fn enregistrer_json_xdg<T: Serialize>(json: T, nom: String, xdg_type: XDG) -> Result<T, Erreur> {
     match determiner_chemin(nom.clone(), xdg_type) {
         Ok(chemin) => enregistrer_json(json, chemin),
-        Err(erreur) => Err(
-            Erreur::EnregistrementImpossible(
-                ErreurEnregistrementImpossible { fichier: nom, erreur }
-            )
-        ),
+        Err(erreur) => ErreurEnregistrementImpossible { fichier: nom, erreur }.as_err(),
     }
 }
 
 pub fn charger_json<T: DeserializeOwned>(chemin_json: PathBuf) -> Result<T, Erreur> {
     let json_serialise = match std::fs::read_to_string(chemin_json.as_path()) {
         Ok(json_serialise) => json_serialise,
-        Err(erreur) => return Err(
-            Erreur::ErreurPermission(ErreurPermission { fichier: chemin_json, erreur })
-        ),
+        Err(erreur) => return ErreurPermission { fichier: chemin_json, erreur }.as_err(),
     };
 
     match serde_json::from_str(&json_serialise) {
-        Ok(json) => return Ok(json),
-        Err(erreur) => return Err(
-            Erreur::JsonInvalide(ErreurJsonInvalide { fichier: chemin_json, erreur })
-        ),
-    };
+        Ok(json) => Ok(json),
+        Err(erreur) => ErreurJsonInvalide { fichier: chemin_json, erreur }.as_err(),
+    }
 }
 
 pub fn charger_json_xdg<T: DeserializeOwned>(nom: String, xdg_type: XDG) -> Result<T, Erreur> {
     match trouver_chemin(nom.clone(), xdg_type) {
         Ok(chemin) => charger_json(chemin),
-        Err(erreur) => Err(
-            Erreur::ChargementImpossible(ErreurChargementImpossible { fichier: nom, erreur })
-        ),
+        Err(erreur) => ErreurChargementImpossible { fichier: nom, erreur }.as_err(),
     }
 }
 
@@ -0,0 +1,564 @@
+// Ligne de commande: chaque sous-commande travaille sur la base sans ouvrir la
+// fenêtre, pour les scripts et les lanceurs externes (Steam, .desktop). Sans
+// sous-commande, la ludothèque démarre comme avant puis ouvre l'interface.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use crate::donnees::config::{LanceursBureau, OrdreRangee, Rangee, SourceRangee};
+use crate::donnees::igdb::extra::obtenir_catalogue;
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{Jeu, JeuIGDB, SurchargeLancement};
+use crate::gui;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::export::FormatExport;
+use crate::outils::medias::TypeMedia;
+use crate::outils::medias::err::ErreurTypeInconnu;
+use crate::outils::arriere_plan::Suivi;
+use crate::outils::{accueil, bureau, detection, export, favoris, import, lanceur, medias, retroarch, scan, steam, synchro};
+use crate::outils::dl::telecharger_couvertures;
+
+#[derive(Parser)]
+#[command(name = "ludotheque", version, about = "Catalogue et lanceur de jeux et d'émulateurs.")]
+pub struct Arguments {
+    /// Écrit les résultats en JSON plutôt qu'en texte (identify, list, show, translate).
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub commande: Option<Commande>,
+}
+
+#[derive(Subcommand)]
+pub enum Commande {
+    /// Cherche les nouveaux jeux, retire les disparus et identifie les nouveaux.
+    #[command(name = "scan")]
+    Scanner,
+
+    /// Identifie de nouveau un jeu, au besoin avec un identifiant IGDB imposé.
+    #[command(name = "identify", alias = "identifier")]
+    Identifier {
+        chemin: PathBuf,
+        /// Identifiant IGDB du jeu (0 pour un jeu inconnu).
+        #[arg(long)]
+        igdb_id: Option<u32>,
+        /// Traduit aussi le nom et les textes du jeu.
+        #[arg(long)]
+        traduire: bool,
+    },
+
+    /// Liste les jeux du catalogue.
+    #[command(name = "list", alias = "lister")]
+    Lister {
+        /// Ne garde que les jeux dont le nom ou le chemin contient ce texte.
+        #[arg(long)]
+        filter: Option<String>,
+        /// Ne garde que les favoris.
+        #[arg(long)]
+        favoris: bool,
+    },
+
+    /// Affiche la fiche d'un jeu et l'émulateur qui le lancerait.
+    #[command(name = "show", alias = "afficher")]
+    Afficher {
+        chemin: String,
+    },
+
+    /// Lance un jeu et attend la fin de la partie.
+    #[command(name = "launch", alias = "lancer")]
+    Lancer {
+        chemin: String,
+    },
+
+    /// Traduit un jeu, ou tous les jeux identifiés qui ne le sont pas encore.
+    #[command(name = "translate", alias = "traduire")]
+    Traduire {
+        chemin: Option<String>,
+    },
+
+    /// Télécharge les médias du catalogue (couvertures et logos par défaut).
+    #[command(name = "download-media", alias = "telecharger")]
+    Telecharger {
+        /// Types de médias (couvertures, illustrations, captures, logos_plateforme,
+        /// logos_entreprise), ou « tous ».
+        types: Vec<String>,
+    },
+
+    /// Exporte le catalogue pour un autre lanceur.
+    #[command(name = "export", alias = "exporter")]
+    Exporter {
+        #[arg(value_enum)]
+        format: FormatExport,
+        dossier: PathBuf,
+    },
+
+    /// Importe les métadonnées d'EmulationStation ou de LaunchBox.
+    #[command(name = "import", alias = "importer")]
+    Importer {
+        /// gamelist.xml, plateforme LaunchBox .xml ou dossier.
+        #[arg(required = true)]
+        chemins: Vec<PathBuf>,
+    },
+
+    /// Rafraîchit les fiches des jeux identifiés depuis leurs fournisseurs.
+    #[command(name = "refresh", alias = "rafraichir")]
+    Rafraichir,
+
+    /// Gère le cache des médias (rapport par défaut).
+    Cache {
+        #[command(subcommand)]
+        action: Option<ActionCache>,
+    },
+
+    /// Ajoute les jeux à Steam comme raccourcis « jeu non-Steam ».
+    Steam {
+        /// Limite l'export à une collection IGDB (slug ou nom).
+        #[arg(long)]
+        collection: Option<String>,
+    },
+
+    /// Écrit les lanceurs .desktop des jeux, après avoir changé le mode si donné.
+    #[command(name = "desktop", alias = "bureau")]
+    Bureau {
+        #[arg(value_enum)]
+        mode: Option<LanceursBureau>,
+    },
+
+    /// Affiche ou modifie les rangées de l'écran d'accueil.
+    #[command(name = "home", alias = "accueil")]
+    Accueil {
+        #[command(subcommand)]
+        action: Option<ActionAccueil>,
+    },
+
+    /// Démarre la ludothèque et ouvre l'interface (par défaut).
+    Gui,
+}
+
+impl Commande {
+    // Sous-commandes qui savent écrire leurs résultats en JSON.
+    fn accepte_json(&self) -> bool {
+        matches!(
+            self,
+            Commande::Identifier { .. } | Commande::Lister { .. } | Commande::Afficher { .. } | Commande::Traduire { .. },
+        )
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ActionAccueil {
+    /// Ajoute une rangée à la fin, ou à la position donnée.
+    #[command(name = "add", alias = "ajouter")]
+    Ajouter {
+        #[arg(value_enum)]
+        source: TypeSource,
+        /// Slug ou nom de la collection, franchise, genre ou plateforme.
+        valeur: Option<String>,
+        #[arg(long)]
+        titre: Option<String>,
+        #[arg(long, value_enum, default_value = "naturel")]
+        ordre: OrdreRangee,
+        #[arg(long, default_value_t = 20)]
+        maximum: u32,
+        /// Position de la rangée, à partir de 1.
+        #[arg(long)]
+        position: Option<usize>,
+    },
+
+    /// Retire la rangée à cette position.
+    #[command(name = "remove", alias = "retirer")]
+    Retirer {
+        position: usize,
+    },
+
+    /// Déplace une rangée.
+    #[command(name = "move", alias = "deplacer")]
+    Deplacer {
+        position: usize,
+        destination: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ActionCache {
+    /// Affiche la place occupée par chaque type de média.
+    #[command(name = "report", alias = "rapport")]
+    Rapport,
+
+    /// Évince les médias les moins récemment utilisés au-delà du budget.
+    #[command(name = "prune", alias = "elaguer")]
+    Elaguer,
+
+    /// Vide le cache de ces types de médias (tous par défaut).
+    #[command(name = "clear", alias = "vider")]
+    Vider {
+        #[arg(value_enum)]
+        types: Vec<TypeMedia>,
+    },
+
+    /// Vide puis télécharge de nouveau ces types de médias (tous par défaut).
+    #[command(name = "download", alias = "telecharger")]
+    Telecharger {
+        #[arg(value_enum)]
+        types: Vec<TypeMedia>,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum TypeSource {
+    Ajoutes,
+    Joues,
+    Favoris,
+    Collection,
+    Franchise,
+    Genre,
+    Plateforme,
+}
+
+// Fiche complète d'un jeu, telle que l'affiche `show`.
+#[derive(Serialize)]
+struct Fiche {
+    jeu: Jeu,
+    igdb: Option<JeuIGDB>,
+    favori: bool,
+    emulateur: Option<String>,
+    regle: Option<String>,
+}
+
+// Les erreurs vont sur la sortie d'erreur, pour ne pas se mêler au JSON.
+fn afficher_json<T: Serialize + ?Sized>(valeur: &T) -> ExitCode {
+    match serde_json::to_string_pretty(valeur) {
+        Ok(texte) => {
+            println!("{}", texte);
+            ExitCode::SUCCESS
+        },
+        Err(erreur) => {
+            eprintln!("ERREUR: {}", erreur);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn resultat<E: TraitErreur>(resultat: Result<(), E>) -> ExitCode {
+    match resultat {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(erreur) => {
+            erreur.afficher_erreur();
+            ExitCode::FAILURE
+        },
+    }
+}
+
+async fn charger_igdb(jeu: &Jeu) -> Option<JeuIGDB> {
+    match jeu.jeu {
+        Some(id) => JeuIGDB::charger_traduit(id).await.ok().flatten(),
+        None => None,
+    }
+}
+
+async fn identifier(chemin: PathBuf, igdb_id: Option<u32>, traduire: bool, json: bool) -> ExitCode {
+    match scan::reidentifier(chemin.clone(), igdb_id, traduire).await {
+        Ok(jeu) if json => afficher_json(&jeu),
+        Ok(jeu) => {
+            println!("INFO: {} identifié comme {} ({}).", chemin.display(), jeu.name, jeu.id);
+            ExitCode::SUCCESS
+        },
+        Err(erreur) => resultat(Err(erreur)),
+    }
+}
+
+async fn lister(filtre: Option<String>, seulement_favoris: bool, json: bool) -> ExitCode {
+    let filtre = filtre.map(|texte| texte.to_lowercase());
+    let favoris = favoris::favoris().await;
+
+    let jeux: Vec<Jeu> = obtenir_catalogue().await
+        .into_iter()
+        .filter(|jeu| !seulement_favoris || favoris.contains(&jeu.chemin))
+        .filter(|jeu| match &filtre {
+            Some(texte) => jeu.nom.to_lowercase().contains(texte) || jeu.chemin.to_lowercase().contains(texte),
+            None => true,
+        })
+        .collect();
+
+    if json {
+        return afficher_json(&jeux);
+    }
+
+    for jeu in &jeux {
+        println!("{}\t{}", jeu.nom, jeu.chemin);
+    }
+
+    ExitCode::SUCCESS
+}
+
+async fn afficher(chemin: String, json: bool) -> ExitCode {
+    let jeu = match Jeu::charger(chemin.clone()).await {
+        Ok(Some(valeur)) => valeur,
+        Ok(None) => {
+            eprintln!("ERREUR: {} n'est pas dans le catalogue.", chemin);
+            return ExitCode::FAILURE;
+        },
+        Err(erreur) => return resultat(Err(erreur)),
+    };
+
+    let surcharge = match SurchargeLancement::charger(chemin.clone()).await {
+        Ok(Some(valeur)) => valeur,
+        _ => SurchargeLancement::vide(chemin.clone()),
+    };
+
+    let (emulateur, regle) = match lanceur::obtenir_commande(&chemin, &surcharge).await {
+        Ok(resolution) => (Some(resolution.emulateur.nom), Some(resolution.regle.to_string())),
+        Err(_) => (None, None),
+    };
+
+    let fiche = Fiche {
+        igdb: charger_igdb(&jeu).await,
+        favori: favoris::est_favori(&chemin).await,
+        jeu,
+        emulateur,
+        regle,
+    };
+
+    if json {
+        return afficher_json(&fiche);
+    }
+
+    println!("{}", fiche.jeu.nom);
+    println!("Chemin: {}", fiche.jeu.chemin);
+    println!("Langue: {}", fiche.jeu.langue);
+
+    if let Some(igdb) = &fiche.igdb {
+        println!("Jeu: {} ({})", igdb.name, igdb.id);
+
+        if let Some(resume) = igdb.summary_traduit.as_ref().or(igdb.summary.as_ref()) {
+            println!("Résumé: {}", resume);
+        }
+    }
+
+    println!("Favori: {}", if fiche.favori { "oui" } else { "non" });
+
+    match &fiche.regle {
+        Some(regle) => println!("Émulateur: {}", regle),
+        None => println!("Émulateur: aucun émulateur compatible."),
+    }
+
+    ExitCode::SUCCESS
+}
+
+async fn traduire(chemin: Option<String>, json: bool) -> ExitCode {
+    let chemins = match chemin {
+        Some(valeur) => vec![valeur],
+        None => {
+            let mut chemins: Vec<String> = vec![];
+
+            for jeu in obtenir_catalogue().await {
+                let a_traduire = match jeu.jeu {
+                    Some(id) if id > 0 => matches!(JeuIGDB::charger(id).await, Ok(Some(igdb)) if igdb.name_traduit.is_none()),
+                    _ => false,
+                };
+
+                if a_traduire {
+                    chemins.push(jeu.chemin);
+                }
+            }
+
+            chemins
+        },
+    };
+
+    let mut traduits: Vec<JeuIGDB> = vec![];
+    let mut code = ExitCode::SUCCESS;
+
+    for chemin in chemins {
+        match scan::traduire_jeu(&chemin).await {
+            Ok(jeu) => {
+                if !json {
+                    println!("INFO: {} traduit: {}.", chemin, jeu.name);
+                }
+                traduits.push(jeu);
+            },
+            Err(erreur) => {
+                erreur.afficher_erreur();
+                code = ExitCode::FAILURE;
+            },
+        }
+    }
+
+    if json && afficher_json(&traduits) == ExitCode::FAILURE {
+        return ExitCode::FAILURE;
+    }
+
+    code
+}
+
+async fn telecharger(types: Vec<String>) -> ExitCode {
+    if types.is_empty() {
+        telecharger_couvertures(&Suivi::default()).await;
+        medias::telecharger_medias(&[TypeMedia::LogoPlateforme, TypeMedia::LogoEntreprise], &Suivi::default()).await;
+        return ExitCode::SUCCESS;
+    }
+
+    let mut demandes: Vec<TypeMedia> = vec![];
+
+    for nom in &types {
+        match nom.as_str() {
+            "tous" => demandes = TypeMedia::tous(),
+            autre => match TypeMedia::depuis_dossier(autre) {
+                Some(type_media) if !demandes.contains(&type_media) => demandes.push(type_media),
+                Some(_) => (),
+                None => {
+                    let mut types: Vec<&str> = TypeMedia::tous().iter().map(|type_media| type_media.dossier()).collect();
+                    types.push("tous");
+                    return resultat(ErreurTypeInconnu { type_media: String::from(autre), types }.as_err());
+                },
+            },
+        }
+    }
+
+    medias::telecharger_medias(&demandes, &Suivi::default()).await;
+    ExitCode::SUCCESS
+}
+
+fn source_rangee(source: TypeSource, valeur: Option<String>) -> Result<SourceRangee, String> {
+    let groupe = |construire: fn(String) -> SourceRangee| match valeur.clone() {
+        Some(valeur) => Ok(construire(valeur)),
+        None => Err(String::from("Cette rangée demande un slug ou un nom.")),
+    };
+
+    match source {
+        TypeSource::Ajoutes => Ok(SourceRangee::RecemmentAjoutes),
+        TypeSource::Joues => Ok(SourceRangee::RecemmentJoues),
+        TypeSource::Favoris => Ok(SourceRangee::Favoris),
+        TypeSource::Collection => groupe(SourceRangee::Collection),
+        TypeSource::Franchise => groupe(SourceRangee::Franchise),
+        TypeSource::Genre => groupe(SourceRangee::Genre),
+        TypeSource::Plateforme => groupe(SourceRangee::Plateforme),
+    }
+}
+
+// Les positions sont données à partir de 1, comme dans la liste affichée.
+fn indice(position: usize, rangees: &[Rangee]) -> Result<usize, String> {
+    match (1..=rangees.len()).contains(&position) {
+        true => Ok(position - 1),
+        false => Err(format!("Pas de rangée {} (1 à {}).", position, rangees.len())),
+    }
+}
+
+fn modifier_accueil(action: ActionAccueil) -> ExitCode {
+    let modification = match action {
+        ActionAccueil::Ajouter { source, valeur, titre, ordre, maximum, position } => accueil::modifier(|rangees| {
+            let rangee = Rangee { titre, source: source_rangee(source, valeur)?, ordre, maximum };
+            let position = position.unwrap_or(rangees.len() + 1).clamp(1, rangees.len() + 1);
+            rangees.insert(position - 1, rangee);
+            Ok(())
+        }),
+        ActionAccueil::Retirer { position } => accueil::modifier(|rangees| {
+            rangees.remove(indice(position, rangees)?);
+            Ok(())
+        }),
+        ActionAccueil::Deplacer { position, destination } => accueil::modifier(|rangees| {
+            let rangee = rangees.remove(indice(position, rangees)?);
+            let destination = destination.clamp(1, rangees.len() + 1);
+            rangees.insert(destination - 1, rangee);
+            Ok(())
+        }),
+    };
+
+    match modification {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("ERREUR: {}", message);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+// Depuis un terminal, la détection des émulateurs précède l'interface, qui lance
+// ensuite le scan et les téléchargements en arrière-plan.
+fn demarrer() {
+    detection::proposer_emulateurs();
+
+    retroarch::synchroniser_coeurs();
+
+    gui::lancer_application();
+}
+
+pub async fn executer(arguments: Arguments) -> ExitCode {
+    let json = arguments.json;
+
+    if json && !arguments.commande.as_ref().is_some_and(Commande::accepte_json) {
+        eprintln!("ERREUR: --json n'est accepté que par identify, list, show et translate.");
+        return ExitCode::FAILURE;
+    }
+
+    match arguments.commande {
+        None | Some(Commande::Gui) => demarrer(),
+        Some(Commande::Scanner) => scan::scanner(&Suivi::default()).await,
+        Some(Commande::Identifier { chemin, igdb_id, traduire }) => return identifier(chemin, igdb_id, traduire, json).await,
+        Some(Commande::Lister { filter, favoris }) => return lister(filter, favoris, json).await,
+        Some(Commande::Afficher { chemin }) => return afficher(chemin, json).await,
+        Some(Commande::Lancer { chemin }) => return resultat(lanceur::lancer_jeu_et_attendre(chemin).await),
+        Some(Commande::Traduire { chemin }) => return traduire(chemin, json).await,
+        Some(Commande::Telecharger { types }) => return telecharger(types).await,
+        Some(Commande::Exporter { format, dossier }) => return resultat(export::commande(format, &dossier).await),
+        Some(Commande::Importer { chemins }) => return resultat(import::commande(&chemins).await),
+        Some(Commande::Rafraichir) => return resultat(synchro::synchroniser(&Suivi::default()).await),
+        Some(Commande::Cache { action: None | Some(ActionCache::Rapport) }) => medias::cache::rapport(),
+        Some(Commande::Cache { action: Some(ActionCache::Elaguer) }) => medias::cache::appliquer_budget().await,
+        Some(Commande::Cache { action: Some(ActionCache::Vider { types }) }) => return resultat(medias::cache::vider_types(&types).await),
+        Some(Commande::Cache { action: Some(ActionCache::Telecharger { types }) }) => return resultat(medias::cache::retelecharger(&types).await),
+        Some(Commande::Steam { collection }) => return resultat(steam::exporter(collection.as_deref()).await),
+        Some(Commande::Bureau { mode }) => return resultat(bureau::commande(mode).await),
+        Some(Commande::Accueil { action: None }) => accueil::afficher(),
+        Some(Commande::Accueil { action: Some(action) }) => return modifier_accueil(action),
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn definition() {
+        Arguments::command().debug_assert();
+    }
+
+    #[test]
+    fn alias_des_lanceurs() {
+        // Les raccourcis Steam et les .desktop déjà écrits appellent `ludotheque lancer`.
+        let arguments = Arguments::parse_from(["ludotheque", "lancer", "/jeux/Zelda.sfc"]);
+        assert!(matches!(arguments.commande, Some(Commande::Lancer { chemin }) if chemin == "/jeux/Zelda.sfc"));
+
+        let arguments = Arguments::parse_from(["ludotheque", "identify", "/jeux/Zelda.sfc", "--igdb-id", "1025", "--json"]);
+        assert!(arguments.json);
+        assert!(matches!(arguments.commande, Some(Commande::Identifier { igdb_id: Some(1025), traduire: false, .. })));
+    }
+
+    #[test]
+    fn json_refuse() {
+        let arguments = Arguments::parse_from(["ludotheque", "list", "--json"]);
+        assert!(arguments.commande.as_ref().is_some_and(Commande::accepte_json));
+
+        let arguments = Arguments::parse_from(["ludotheque", "export", "pegasus", "/tmp", "--json"]);
+        assert!(!arguments.commande.as_ref().is_some_and(Commande::accepte_json));
+    }
+
+    #[test]
+    fn cache() {
+        let arguments = Arguments::parse_from(["ludotheque", "cache", "vider", "couvertures", "logos_plateforme"]);
+        assert!(matches!(
+            arguments.commande,
+            Some(Commande::Cache { action: Some(ActionCache::Vider { types }) })
+                if types == [TypeMedia::Couverture, TypeMedia::LogoPlateforme]
+        ));
+
+        assert!(Arguments::try_parse_from(["ludotheque", "cache", "vider", "inconnus"]).is_err());
+        assert!(Arguments::try_parse_from(["ludotheque", "cache", "purger"]).is_err());
+    }
+}
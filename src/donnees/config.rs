@@ -8,10 +8,90 @@ use xdg::BaseDirectories;
 pub struct LudothequeConfig {
     pub utilisateurs: Vec<Utilisateur>,
     pub emulateurs: Vec<Emulateur>,
+    #[serde(default)]
+    pub emulateurs_plateforme: Vec<EmulateurPlateforme>,
+    // Émulateurs détectés que l'utilisateur a refusés: ils ne sont plus proposés.
+    #[serde(default)]
+    pub emulateurs_refuses: Vec<String>,
     pub repertoire_jeux: Option<PathBuf>,
     pub repertoire_donnees: Option<PathBuf>,
     pub  profondeur_recherche: Option<u32>,
     pub langue: String,
+    #[serde(default)]
+    pub retroarch: Option<ConfigRetroArch>,
+    #[serde(default)]
+    pub crochets: Crochets,
+    #[serde(default)]
+    pub jeux_natifs: bool,
+    // Seuls les exécutables de ces dossiers sont des jeux natifs; ailleurs, les
+    // .desktop et AppImage suffisent.
+    #[serde(default)]
+    pub dossiers_natifs: Vec<PathBuf>,
+    #[serde(default)]
+    pub wine: Option<ConfigWine>,
+    #[serde(default)]
+    pub proton: Option<ConfigProton>,
+    #[serde(default)]
+    pub cache_medias: ConfigCache,
+    #[serde(default)]
+    pub igdb: ConfigIGDB,
+    #[serde(default)]
+    pub serveurs: ConfigServeurs,
+    // Ordre de consultation des fournisseurs de métadonnées.
+    #[serde(default = "fournisseurs_defaut")]
+    pub fournisseurs: Vec<Fournisseur>,
+    // Jeux ayant un lanceur .desktop dans ~/.local/share/applications.
+    #[serde(default)]
+    pub lanceurs_bureau: LanceursBureau,
+    // Rangées de l'écran d'accueil, dans l'ordre d'affichage.
+    #[serde(default = "accueil_defaut")]
+    pub accueil: Vec<Rangee>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SourceRangee {
+    RecemmentAjoutes,
+    RecemmentJoues,
+    Favoris,
+    // Slug ou nom (original ou traduit) de la collection, franchise, etc.
+    Collection(String),
+    Franchise(String),
+    Genre(String),
+    Plateforme(String),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum OrdreRangee {
+    // Celui de la source: date d'ajout, de partie, ou de sortie pour une série.
+    #[default]
+    Naturel,
+    Nom,
+    Note,
+    Sortie,
+    Aleatoire,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rangee {
+    // Par défaut, le nom de la source.
+    #[serde(default)]
+    pub titre: Option<String>,
+    pub source: SourceRangee,
+    #[serde(default)]
+    pub ordre: OrdreRangee,
+    #[serde(default = "maximum_rangee")]
+    pub maximum: u32,
+}
+
+fn maximum_rangee() -> u32 {
+    20
+}
+
+fn accueil_defaut() -> Vec<Rangee> {
+    [SourceRangee::RecemmentJoues, SourceRangee::Favoris, SourceRangee::RecemmentAjoutes]
+        .into_iter()
+        .map(|source| Rangee { titre: None, source, ordre: OrdreRangee::Naturel, maximum: maximum_rangee() })
+        .collect()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +106,189 @@ pub struct Emulateur {
     pub nom: String,
     pub commande: String,
     pub extensions: Vec<String>,
+    #[serde(default)]
+    pub priorite: i32,
+    #[serde(default)]
+    pub plateformes: Vec<u32>,
+    #[serde(default)]
+    pub type_emulateur: TypeEmulateur,
+    #[serde(default)]
+    pub coeur: Option<PathBuf>,
+    #[serde(default)]
+    pub crochets: Crochets,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum TypeEmulateur {
+    #[default]
+    Commande,
+    RetroArch,
+    Natif,
+    Desktop,
+    AppImage,
+    Wine,
+    Proton,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Fournisseur {
+    IGDB,
+    Local,
+}
+
+fn fournisseurs_defaut() -> Vec<Fournisseur> {
+    vec![Fournisseur::Local, Fournisseur::IGDB]
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum LanceursBureau {
+    #[default]
+    Aucun,
+    Favoris,
+    Tous,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmulateurPlateforme {
+    pub plateforme: u32,
+    pub emulateur: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Crochets {
+    #[serde(default)]
+    pub avant: Vec<Crochet>,
+    #[serde(default)]
+    pub apres: Vec<Crochet>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Crochet {
+    pub commande: String,
+    #[serde(default)]
+    pub delai: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigRetroArch {
+    pub commande: String,
+    #[serde(default)]
+    pub repertoire_coeurs: Option<PathBuf>,
+    #[serde(default)]
+    pub repertoire_infos: Option<PathBuf>,
+    #[serde(default)]
+    pub importer_listes: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigWine {
+    pub commande: String,
+    #[serde(default)]
+    pub prefixe: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigProton {
+    pub commande: PathBuf,
+    pub prefixe: PathBuf,
+    #[serde(default)]
+    pub steam: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigCache {
+    // Budget en mégaoctets des médias en cache (hors couvertures du catalogue).
+    pub budget: u64,
+}
+
+impl Default for ConfigCache {
+    fn default() -> Self {
+        ConfigCache { budget: 512 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigIGDB {
+    // Durée de vie en jours des réponses IGDB en cache (0 pour toujours interroger IGDB).
+    #[serde(default = "duree_cache_defaut")]
+    pub duree_cache: u64,
+    // Ne répond que depuis le cache et les fixtures, sans jamais accéder au réseau.
+    #[serde(default)]
+    pub hors_ligne: bool,
+    // Réponses enregistrées à l'avance (même format que le cache), consultées en premier.
+    #[serde(default)]
+    pub fixtures: Option<PathBuf>,
+}
+
+fn duree_cache_defaut() -> u64 {
+    30
+}
+
+impl Default for ConfigIGDB {
+    fn default() -> Self {
+        ConfigIGDB { duree_cache: duree_cache_defaut(), hors_ligne: false, fixtures: None }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigServeur {
+    pub url: String,
+    // Délai maximal d'une requête en secondes (0 pour aucun).
+    #[serde(default)]
+    pub delai: u64,
+    // Ex: http://proxy:3128 ou socks5://127.0.0.1:1080.
+    #[serde(default)]
+    pub proxy: Option<String>,
+}
+
+impl ConfigServeur {
+    fn new(url: &str) -> Self {
+        ConfigServeur { url: String::from(url), delai: 30, proxy: None }
+    }
+
+    pub fn url(&self, chemin: &str) -> String {
+        format!("{}/{}", self.url.trim_end_matches('/'), chemin.trim_start_matches('/'))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigServeurs {
+    #[serde(default = "serveur_igdb")]
+    pub igdb: ConfigServeur,
+    #[serde(default = "serveur_twitch")]
+    pub twitch: ConfigServeur,
+    #[serde(default = "serveur_openai")]
+    pub openai: ConfigServeur,
+    // Images IGDB (couvertures, illustrations, logos).
+    #[serde(default = "serveur_medias")]
+    pub medias: ConfigServeur,
+}
+
+fn serveur_igdb() -> ConfigServeur {
+    ConfigServeur::new("https://api.igdb.com/v4")
+}
+
+fn serveur_twitch() -> ConfigServeur {
+    ConfigServeur::new("https://id.twitch.tv")
+}
+
+fn serveur_openai() -> ConfigServeur {
+    ConfigServeur::new("https://api.openai.com/v1")
+}
+
+fn serveur_medias() -> ConfigServeur {
+    ConfigServeur::new("https://images.igdb.com/igdb/image/upload")
+}
+
+impl Default for ConfigServeurs {
+    fn default() -> Self {
+        ConfigServeurs {
+            igdb: serveur_igdb(),
+            twitch: serveur_twitch(),
+            openai: serveur_openai(),
+            medias: serveur_medias(),
+        }
+    }
 }
 
 pub fn config_par_defaut() -> LudothequeConfig {
@@ -38,15 +301,29 @@ pub fn config_par_defaut() -> LudothequeConfig {
                 Utilisateur{id: 0, nom: String::from("Utilisateur 0"), pseudonyme: String::from("user0")}
             ],
         emulateurs: vec![],
+        emulateurs_plateforme: vec![],
+        emulateurs_refuses: vec![],
         repertoire_jeux: dirs::home_dir(),
         profondeur_recherche: Some(1),
         repertoire_donnees: Some(xdg_dirs.get_data_home()),
-        langue: format!("French"),
+        langue: String::from("French"),
+        retroarch: None,
+        crochets: Crochets::default(),
+        jeux_natifs: false,
+        dossiers_natifs: vec![],
+        wine: None,
+        proton: None,
+        cache_medias: ConfigCache::default(),
+        igdb: ConfigIGDB::default(),
+        serveurs: ConfigServeurs::default(),
+        fournisseurs: fournisseurs_defaut(),
+        lanceurs_bureau: LanceursBureau::default(),
+        accueil: accueil_defaut(),
     }
 }
 
-pub fn creer_config() -> Result<LudothequeConfig, json::err::Erreur> {
-    json::enregistrer_json_xdg(config_par_defaut(), String::from("config.json"), chemins::XDG::CONFIG)
+pub fn enregistrer_config(config: LudothequeConfig) -> Result<LudothequeConfig, json::err::Erreur> {
+    json::enregistrer_json_xdg(config, String::from("config.json"), chemins::XDG::CONFIG)
 }
 
 pub fn charger_config() -> Result<LudothequeConfig, json::err::Erreur> {
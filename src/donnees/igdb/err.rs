@@ -14,7 +14,7 @@ impl ErreurLocalisationDB {
 
 impl TraitErreur for ErreurLocalisationDB {
     fn message(&self) -> String {
-        format!("Impossible de determiner le chemin de \"games.db\".")
+        String::from("Impossible de determiner le chemin de \"games.db\".")
     }
 
     fn cause(&self) -> Option<String> {
@@ -35,7 +35,7 @@ impl ErreurAccesDB {
 
 impl TraitErreur for ErreurAccesDB {
     fn message(&self) -> String{
-        format!("Impossible d'accéder à \"games.db\".")
+        String::from("Impossible d'accéder à \"games.db\".")
     }
 
     fn cause(&self) -> Option<String> {
@@ -56,7 +56,7 @@ impl ErreurCreationDB {
 
 impl TraitErreur for ErreurCreationDB {
     fn message(&self) -> String {
-        format!("Impossible de creer la base de données \"games.db\".")
+        String::from("Impossible de creer la base de données \"games.db\".")
     }
 
     fn cause(&self) -> Option<String> {
@@ -14,13 +14,9 @@ pub async fn obtenir_catalogue() -> Vec<Jeu> {
     }
 }
 
-pub async fn obtenir_jeux_async(filtre: &str) -> Vec<Jeu> {
-    match sqlx::query_as::<_, Jeu>(
-        &format!(
-            "SELECT * FROM catalogue LEFT JOIN jeux ON catalogue.jeu = jeux.id
-            WHERE {};",
-            filtre,
-        ),
+pub async fn obtenir_plateformes(jeu: u32) -> Vec<u32> {
+    match sqlx::query_scalar::<_, u32>(
+        &format!("SELECT plateforme FROM jeux_plateformes WHERE jeu = {};", jeu)
     ).fetch_all(&obtenir_db().await.unwrap()).await {
         Ok(liste) => liste,
         Err(erreur) => {
@@ -30,8 +26,26 @@ pub async fn obtenir_jeux_async(filtre: &str) -> Vec<Jeu> {
     }
 }
 
-pub fn obtenir_jeux_par(filtre: &str) -> Vec<Jeu> {
-    async_std::task::block_on(async {
-        obtenir_jeux_async(filtre).await
-    })
+pub async fn obtenir_entreprises(jeu: u32) -> Vec<u32> {
+    match sqlx::query_scalar::<_, u32>(
+        &format!("SELECT entreprise FROM jeux_entreprises WHERE jeu = {};", jeu)
+    ).fetch_all(&obtenir_db().await.unwrap()).await {
+        Ok(liste) => liste,
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            vec![]
+        },
+    }
+}
+
+pub async fn obtenir_plateformes_catalogue() -> Vec<u32> {
+    match sqlx::query_scalar::<_, u32>(
+        "SELECT DISTINCT plateforme FROM jeux_plateformes WHERE jeu IN (SELECT jeu FROM catalogue);"
+    ).fetch_all(&obtenir_db().await.unwrap()).await {
+        Ok(liste) => liste,
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            vec![]
+        },
+    }
 }
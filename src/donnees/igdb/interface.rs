@@ -58,6 +58,22 @@ pub trait CompatibleSQL<'a, U: ValeurSQL + Send>: Sized + Send {
         Ok(())
     }
 
+    // Insertion qui met à jour la ligne de même id si elle existe déjà.
+    fn commande_remplacer(&self) -> String {
+        en_upsert(&self.commande_enregistrer())
+    }
+
+    // Écrase la ligne existante sur place, ex: pour des métadonnées rafraîchies. La
+    // ligne n'est pas supprimée: les tables qui la référencent restent valides.
+    async fn remplacer(&self) -> Result<(), Erreur> {
+        match sqlx::query(
+            &self.commande_remplacer()
+        ).execute(&obtenir_db().await?).await {
+            Ok(_) => Ok(()),
+            Err(erreur) => ErreurEnregistrementImpossible { erreur, objet: Self::table() }.as_err(),
+        }
+    }
+
     async fn traduire(&self) -> Result<(), Erreur> {
         match sqlx::query(
             &self.commande_traduire()
@@ -68,6 +84,26 @@ pub trait CompatibleSQL<'a, U: ValeurSQL + Send>: Sized + Send {
     }
 }
 
+// INSERT INTO t ("id", "a", ...) VALUES (...) ON CONFLICT ("id") DO UPDATE SET "a" = excluded."a", ...
+fn en_upsert(commande: &str) -> String {
+    let commande = commande.trim().trim_end_matches(';');
+
+    let colonnes: Vec<&str> = match (commande.find('('), commande.find(')')) {
+        (Some(debut), Some(fin)) if debut < fin => commande[debut + 1..fin]
+            .split(',')
+            .map(|colonne| colonne.trim())
+            .filter(|colonne| !colonne.is_empty() && colonne.trim_matches('"') != "id")
+            .collect(),
+        _ => vec![],
+    };
+
+    let affectations: Vec<String> = colonnes.iter()
+        .map(|colonne| format!("{0} = excluded.{0}", colonne))
+        .collect();
+
+    format!("{} ON CONFLICT (\"id\") DO UPDATE SET {};", commande, affectations.join(", "))
+}
+
 fn guillemeter(input: String) -> String {
     format!("'{}'", input.replace("'", "''"))
 }
@@ -177,12 +213,11 @@ async fn charger_vec_id<T: for<'a> CompatibleSQL<'a, u32>>(
         Err(erreur) => return ErreurChargementImpossible { erreur, objet: table, id }.as_err(),
     };
     for ligne in res.iter() {
-        match ligne.get::<Option<u32>, &str>(champ) {
-            Some(id) => liste.push(id),
-            None => {},
+        if let Some(id) = ligne.get::<Option<u32>, &str>(champ) {
+            liste.push(id);
         }
     }
-    if liste.len() > 0 {
+    if !liste.is_empty() {
         Ok(Some(liste))
     } else {
         Ok(None)
@@ -202,15 +237,13 @@ async fn charger_vec<T: for<'a> CompatibleSQL<'a, u32>>(
         Err(erreur) => return ErreurChargementImpossible { erreur, objet: table, id }.as_err(),
     };
     for ligne in res.iter() {
-        match ligne.get::<Option<u32>, &str>(champ) {
-            Some(id) => match T::charger(id).await? {
-                Some(valeur) => liste.push(valeur),
-                None => {},
-            },
-            None => {},
+        if let Some(id) = ligne.get::<Option<u32>, &str>(champ) {
+            if let Some(valeur) = T::charger(id).await? {
+                liste.push(valeur);
+            }
         }
     }
-    if liste.len() > 0 {
+    if !liste.is_empty() {
         Ok(Some(liste))
     } else {
         Ok(None)
@@ -367,12 +400,12 @@ impl CompatibleSQL<'_, u32> for CategorieJeuIGDB {
 
     fn commande_enregistrer(&self) -> String {
         println!("ATTENTION: Impossible d'enregistrer une catégorie de jeu.");
-        format!("")
+        String::new()
     }
 
     fn commande_traduire(&self) -> String {
         println!("ATTENTION: Impossible de traduire une catégorie de jeu.");
-        format!("")
+        String::new()
     }
 
     fn commande_charger<T: ValeurSQL>(id: T) -> String {
@@ -432,7 +465,7 @@ impl CompatibleSQL<'_, u32> for CouvertureIGDB {
 
     fn commande_traduire(&self) -> String {
         println!("ATTENTION: Impossible de traduire une couverture.");
-        format!("")
+        String::new()
     }
 
     fn commande_charger<T: ValeurSQL>(id: T) -> String {
@@ -665,6 +698,11 @@ impl CompatibleSQL<'_, u32> for JeuIGDB {
         }
     }
 
+    // Les correspondances (genres, plateformes…) sont réécrites avec la ligne.
+    async fn remplacer(&self) -> Result<(), Erreur> {
+        self.inserer().await
+    }
+
     async fn inserer(&self) -> Result<(), Erreur> {
         async fn supprimer(db: &Pool<Sqlite>, id: u32, table: &str) -> Result<(), Erreur> {
             match sqlx::query(
@@ -768,7 +806,7 @@ impl CompatibleSQL<'_, u32> for JeuIGDB {
 
         let _ = inserer(&db, self.id, self.similar_games.clone().unwrap_or(vec![]), "jeux_similaires", "jeu_similaire").await?;
 
-        let _ = inserer(&db, self.id, self.platforms.clone().unwrap_or(vec![]), "jeux_genres", "genre").await?;
+        let _ = inserer(&db, self.id, self.platforms.clone().unwrap_or(vec![]), "jeux_plateformes", "plateforme").await?;
 
         let liste: Vec<u32> = self.artworks.clone().unwrap_or(vec![]).iter().map(|x| x.id).collect();
         let _ = inserer(&db, self.id, liste, "jeux_illustrations", "illustration").await?;
@@ -780,7 +818,7 @@ impl CompatibleSQL<'_, u32> for JeuIGDB {
         let _ = inserer(&db, self.id, liste, "jeux_videos", "video").await?;
 
         match sqlx::query(
-            &self.commande_enregistrer()
+            &self.commande_remplacer()
         ).execute(&obtenir_db().await?).await {
             Ok(_) => Ok(()),
             Err(erreur) => ErreurEnregistrementImpossible { erreur, objet: JeuIGDB::table() }.as_err(),
@@ -1020,7 +1058,7 @@ impl CompatibleSQL<'_, u32> for IllustrationIGDB {
 
     fn commande_traduire(&self) -> String {
         println!("ATTENTION: Impossible de traduire une illustration.");
-        format!("")
+        String::new()
     }
 
     fn commande_charger<T: ValeurSQL>(id: T) -> String {
@@ -1071,7 +1109,7 @@ impl CompatibleSQL<'_, u32> for CaptureEcranIGDB {
 
     fn commande_traduire(&self) -> String {
         println!("ATTENTION: Impossible de traduire une capture d'écran.");
-        format!("")
+        String::new()
     }
 
     fn commande_charger<T: ValeurSQL>(id: T) -> String {
@@ -1175,12 +1213,12 @@ impl CompatibleSQL<'_, u32> for CategoriePlateformeIGDB {
 
     fn commande_enregistrer(&self) -> String {
         println!("ATTENTION: Impossible d'enregistrer une catégorie de plateforme.");
-        format!("")
+        String::new()
     }
 
     fn commande_traduire(&self) -> String {
         println!("ATTENTION: Impossible de traduire une catégorie de plateforme.");
-        format!("")
+        String::new()
     }
 
     fn commande_charger<T: ValeurSQL>(id: T) -> String {
@@ -1240,7 +1278,7 @@ impl CompatibleSQL<'_, u32> for LogoPlateformeIGDB {
 
     fn commande_traduire(&self) -> String {
         println!("ATTENTION: Impossible de traduire le logo d'une plateforme.");
-        format!("")
+        String::new()
     }
 
     fn commande_charger<T: ValeurSQL>(id: T) -> String {
@@ -1386,6 +1424,23 @@ impl CompatibleSQL<'_, u32> for PlateformeIGDB {
             None => Ok(None),
         }
     }
+
+    async fn remplacer(&self) -> Result<(), Erreur> {
+        self.inserer().await
+    }
+
+    async fn inserer(&self) -> Result<(), Erreur> {
+        if let Some(logo) = &self.platform_logo {
+            logo.enregistrer().await?;
+        }
+
+        match sqlx::query(
+            &self.commande_remplacer()
+        ).execute(&obtenir_db().await?).await {
+            Ok(_) => Ok(()),
+            Err(erreur) => ErreurEnregistrementImpossible { erreur, objet: PlateformeIGDB::table() }.as_err(),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -1413,7 +1468,7 @@ impl CompatibleSQL<'_, u32> for LogoEntrepriseIGDB {
 
     fn commande_traduire(&self) -> String {
         println!("ATTENTION: Impossible de traduire le logo d'une entreprise.");
-        format!("")
+        String::new()
     }
 
     fn commande_charger<T: ValeurSQL>(id: T) -> String {
@@ -1530,18 +1585,13 @@ impl CompatibleSQL<'_, u32> for EntrepriseIGDB {
             Err(erreur) => return ErreurChargementImpossible { erreur, objet: "jeux_entreprises", id }.as_err(),
         };
         for ligne in res.iter() {
-            match ligne.get::<Option<u32>, &str>("jeu") {
-                Some(id) => {
-                    match ligne.get::<Option<bool>, &str>("developed") {
-                        Some(true) => developed.push(id),
-                        _ => {},
-                    }
-                    match ligne.get::<Option<bool>, &str>("published") {
-                        Some(true) => published.push(id),
-                        _ => {},
-                    }
+            if let Some(id) = ligne.get::<Option<u32>, &str>("jeu") {
+                if let Some(true) = ligne.get::<Option<bool>, &str>("developed") {
+                    developed.push(id);
+                }
+                if let Some(true) = ligne.get::<Option<bool>, &str>("published") {
+                    published.push(id);
                 }
-                None => {},
             }
         }
 
@@ -1598,11 +1648,19 @@ impl CompatibleSQL<'_, u32> for EntrepriseIGDB {
         }
     }
 
+    async fn remplacer(&self) -> Result<(), Erreur> {
+        self.inserer().await
+    }
+
     async fn inserer(&self) -> Result<(), Erreur> {
         let db = obtenir_db().await?;
 
+        if let Some(logo) = &self.logo {
+            logo.enregistrer().await?;
+        }
+
         match sqlx::query(
-            &format!(r#"DELETE * FROM jeux_entreprises WHERE "entreprise" = {};"#, self.id)
+            &format!(r#"DELETE FROM jeux_entreprises WHERE "entreprise" = {};"#, self.id)
         ).execute(&db).await {
             Ok(_) => {},
             Err(erreur) => return ErreurSQL { erreur, desc: "la suppression d'un jeu dans jeux_entreprises"}.as_err(),
@@ -1634,8 +1692,12 @@ impl CompatibleSQL<'_, u32> for EntrepriseIGDB {
 
         let developed = inserer(&db, self.id, self.developed.clone().unwrap_or(vec![]), true, false);
 
-        //FIXME: copy past from top; this makes an infinite loop
-        CompatibleSQL::enregistrer(self).await?;
+        match sqlx::query(
+            &self.commande_remplacer()
+        ).execute(&db).await {
+            Ok(_) => {},
+            Err(erreur) => return ErreurEnregistrementImpossible { erreur, objet: EntrepriseIGDB::table() }.as_err(),
+        }
 
         published.await?;
         developed.await?;
@@ -1657,8 +1719,24 @@ impl CompatibleSQL<'_, String> for Jeu {
     fn commande_enregistrer(&self) -> String {
         format!(
             r#"
-            INSERT INTO catalogue ("jeu", "chemin", "nom", "langue")
-            VALUES ({}, {}, {}, {});
+            INSERT INTO catalogue ("jeu", "chemin", "nom", "langue", "ajoute_le")
+            VALUES ({}, {}, {}, {}, CAST(strftime('%s', 'now') AS INTEGER));
+            "#,
+            self.jeu.convertir(),
+            self.chemin.convertir(),
+            self.nom.convertir(),
+            self.langue.convertir(),
+        )
+    }
+
+    // Le catalogue n'a qu'une ligne par chemin (index unique); la date d'ajout n'est
+    // pas modifiée.
+    fn commande_remplacer(&self) -> String {
+        format!(
+            r#"
+            INSERT INTO catalogue ("jeu", "chemin", "nom", "langue", "ajoute_le")
+            VALUES ({}, {}, {}, {}, CAST(strftime('%s', 'now') AS INTEGER))
+            ON CONFLICT ("chemin") DO UPDATE SET "jeu" = excluded."jeu", "nom" = excluded."nom", "langue" = excluded."langue";
             "#,
             self.jeu.convertir(),
             self.chemin.convertir(),
@@ -1688,3 +1766,73 @@ impl CompatibleSQL<'_, String> for Jeu {
         Jeu::charger(id).await
     }
 }
+
+#[async_trait::async_trait]
+impl CompatibleSQL<'_, String> for SurchargeLancement {
+    fn table() -> &'static str {
+        "surcharges_lancement"
+    }
+
+    fn id(&self) -> String {
+        self.chemin.clone()
+    }
+
+    fn commande_enregistrer(&self) -> String {
+        format!(
+            r#"
+            INSERT OR REPLACE INTO surcharges_lancement ("chemin", "emulateur", "arguments", "environnement", "repertoire", "crochets")
+            VALUES ({}, {}, {}, {}, {}, {});
+            "#,
+            self.chemin.convertir(),
+            self.emulateur.convertir(),
+            self.arguments.convertir(),
+            self.environnement.convertir(),
+            self.repertoire.convertir(),
+            self.crochets.convertir(),
+        )
+    }
+
+    fn commande_traduire(&self) -> String {
+        String::new()
+    }
+
+    fn commande_charger<T: ValeurSQL>(id: T) -> String {
+        format!("SELECT * FROM surcharges_lancement WHERE \"chemin\" = {};", id.convertir())
+    }
+
+    async fn charger(id: String) -> Result<Option<SurchargeLancement>, Erreur> {
+        match sqlx::query_as::<_, SurchargeLancement>(
+            &SurchargeLancement::commande_charger(id)
+        ).fetch_optional(&obtenir_db().await?).await {
+            Ok(valeur) => Ok(valeur),
+            Err(erreur) => ErreurChargementImpossible { erreur, objet: "surcharge de lancement", id: 0 }.as_err(),
+        }
+    }
+
+    async fn charger_traduit(id: String) -> Result<Option<SurchargeLancement>, Erreur> {
+        SurchargeLancement::charger(id).await
+    }
+
+    // La table n'a ni "jeu" ni "id": seule la clé chemin est interrogée.
+    async fn existe<'b>(id: String) -> Result<bool, Erreur> where String: 'b {
+        match sqlx::query("SELECT chemin FROM surcharges_lancement WHERE chemin = ?;")
+            .bind(id)
+            .fetch_optional(&obtenir_db().await?).await {
+            Ok(valeur) => Ok(valeur.is_some()),
+            Err(erreur) => ErreurChargementImpossible { erreur, objet: "surcharge de lancement", id: 0 }.as_err(),
+        }
+    }
+
+    async fn supprimer<'b>(id: String) -> Result<(), Erreur> where String: 'b {
+        match sqlx::query("DELETE FROM surcharges_lancement WHERE chemin = ?;")
+            .bind(id)
+            .execute(&obtenir_db().await?).await {
+            Ok(_) => Ok(()),
+            Err(erreur) => ErreurSuppressionImpossible { erreur, objet: "surcharge de lancement", id: 0 }.as_err(),
+        }
+    }
+
+    async fn enregistrer(&self) -> Result<(), Erreur> {
+        self.inserer().await
+    }
+}
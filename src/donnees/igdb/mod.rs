@@ -8,7 +8,7 @@ use sqlx::{Sqlite, Pool, migrate::MigrateDatabase};
 use crate::donnees::igdb::err::*;
 use crate::chemin::chemins::{determiner_chemin, XDG};
 
-pub fn obtenir_db_url<'a>() -> Result<String, Erreur> {
+pub fn obtenir_db_url() -> Result<String, Erreur> {
     match determiner_chemin("games.db".to_string(), XDG::DATA) {
         Ok(chemin) => Ok(format!("sqlite://{}", chemin.display())),
         Err(erreur) => ErreurLocalisationDB { erreur }.as_err(),
@@ -24,7 +24,7 @@ async fn db_existe() -> Result<bool, Erreur> {
     }
 }
 
-async fn obtenir_db() -> Result<Pool<Sqlite>, Erreur> {
+pub(crate) async fn obtenir_db() -> Result<Pool<Sqlite>, Erreur> {
     match SqlitePool::connect(&obtenir_db_url()?).await {
         Ok(db) => Ok(db),
         Err(erreur) => ErreurAccesDB { erreur }.as_err(),
@@ -35,7 +35,7 @@ pub async fn creer_db() -> Result<(), Erreur> {
     let db_url = obtenir_db_url()?;
 
     if db_existe().await? {
-        return Ok(());
+        return migrer_db().await;
     }
 
     println!("INFO: Création de la base de données {}.", db_url);
@@ -504,5 +504,133 @@ pub async fn creer_db() -> Result<(), Erreur> {
         Err(erreur) => return ErreurSQL { erreur, desc: "l'insertion de jeux"}.as_err(),
     };
 
+    migrer_db().await
+}
+
+async fn ajouter_colonne(db: &Pool<Sqlite>, table: &str, colonne: &str, definition: &str) -> Result<(), Erreur> {
+    let colonnes = match sqlx::query_scalar::<_, String>(
+        &format!("SELECT name FROM pragma_table_info('{}');", table)
+    ).fetch_all(db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la lecture des colonnes d'une table"}.as_err(),
+    };
+
+    if colonnes.iter().any(|nom| nom == colonne) {
+        return Ok(());
+    }
+
+    match sqlx::query(
+        &format!("ALTER TABLE {} ADD COLUMN {} {};", table, colonne, definition)
+    ).execute(db).await {
+        Ok(_) => Ok(()),
+        Err(erreur) => ErreurSQL { erreur, desc: "l'ajout d'une colonne"}.as_err(),
+    }
+}
+
+async fn migrer_db() -> Result<(), Erreur> {
+    let db = obtenir_db().await?;
+
+    let _ = match sqlx::query(
+        "CREATE TABLE IF NOT EXISTS surcharges_lancement (
+            chemin TEXT PRIMARY KEY NOT NULL,
+            emulateur VARCHAR(100),
+            arguments TEXT,
+            environnement TEXT,
+            repertoire TEXT
+        );"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la création de la table surcharges_lancement"}.as_err(),
+    };
+
+    ajouter_colonne(&db, "surcharges_lancement", "crochets", "TEXT").await?;
+
+    let _ = match sqlx::query(
+        "CREATE TABLE IF NOT EXISTS cache_medias (
+            chemin TEXT PRIMARY KEY NOT NULL,
+            type_media VARCHAR(50) NOT NULL,
+            image_id VARCHAR(100) NOT NULL,
+            taille INTEGER NOT NULL,
+            utilise_le INTEGER NOT NULL
+        );"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la création de la table cache_medias"}.as_err(),
+    };
+
+    let _ = match sqlx::query(
+        "CREATE TABLE IF NOT EXISTS images_perso (
+            chemin TEXT NOT NULL,
+            type_image VARCHAR(50) NOT NULL,
+            fichier TEXT NOT NULL,
+
+            PRIMARY KEY (chemin, type_image)
+        );"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la création de la table images_perso"}.as_err(),
+    };
+
+    // Champs de `jeux` remplis par un import (gamelist, LaunchBox), à préserver lors des rafraîchissements IGDB.
+    let _ = match sqlx::query(
+        "CREATE TABLE IF NOT EXISTS sources_metadonnees (
+            jeu INTEGER NOT NULL,
+            champ VARCHAR(50) NOT NULL,
+            source VARCHAR(50) NOT NULL,
+
+            PRIMARY KEY (jeu, champ)
+        );"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la création de la table sources_metadonnees"}.as_err(),
+    };
+
+    let _ = match sqlx::query(
+        "CREATE TABLE IF NOT EXISTS favoris (
+            chemin TEXT PRIMARY KEY NOT NULL,
+            ajoute_le INTEGER NOT NULL
+        );"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la création de la table favoris"}.as_err(),
+    };
+
+    let _ = match sqlx::query(
+        "CREATE TABLE IF NOT EXISTS parties (
+            chemin TEXT PRIMARY KEY NOT NULL,
+            jouee_le INTEGER NOT NULL,
+            nombre INTEGER NOT NULL DEFAULT 0
+        );"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la création de la table parties"}.as_err(),
+    };
+
+    // Une ligne par chemin: une entrée du catalogue est réécrite sur place, jamais supprimée puis recréée.
+    let _ = match sqlx::query(
+        "DELETE FROM catalogue WHERE rowid NOT IN (SELECT max(rowid) FROM catalogue GROUP BY chemin);"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "le dédoublonnage du catalogue"}.as_err(),
+    };
+
+    let _ = match sqlx::query(
+        "CREATE UNIQUE INDEX IF NOT EXISTS catalogue_chemin ON catalogue (chemin);"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la création de l'index du catalogue"}.as_err(),
+    };
+
+    // Date d'ajout au catalogue, conservée quand l'entrée est réécrite. Les entrées
+    // antérieures gardent l'ordre de leur rowid, avant toutes les nouvelles.
+    ajouter_colonne(&db, "catalogue", "ajoute_le", "INTEGER").await?;
+
+    let _ = match sqlx::query(
+        "UPDATE catalogue SET ajoute_le = rowid WHERE ajoute_le IS NULL;"
+    ).execute(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurSQL { erreur, desc: "la datation du catalogue"}.as_err(),
+    };
+
     Ok(())
 }
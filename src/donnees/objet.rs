@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
-#[derive(Debug, Clone, FromRow)]
+use crate::donnees::config::Crochets;
+
+#[derive(Debug, Clone, FromRow, Serialize)]
 pub struct Jeu {
     pub jeu: Option<u32>,
 //    pub jeu: Option<JeuIGDB>,
@@ -84,6 +86,8 @@ pub struct FranchiseIGDB /*<'static>*/ {
     pub updated_at: Option<i64>,
 }
 
+// Table de référence IGDB, chargée par CompatibleSQL mais lue nulle part pour l'instant.
+#[allow(dead_code)]
 #[derive(Debug, Clone, FromRow)]
 pub struct CategorieJeuIGDB {
     pub id: u32,
@@ -146,6 +150,8 @@ pub struct PlateformeIGDB /*<'static>*/ {
     pub updated_at: Option<i64>,
 }
 
+// Table de référence IGDB, chargée par CompatibleSQL mais lue nulle part pour l'instant.
+#[allow(dead_code)]
 #[derive(Debug, Clone, FromRow)]
 pub struct CategoriePlateformeIGDB {
     pub id: u32,
@@ -237,3 +243,61 @@ pub struct VideoIGDB /*<'static>*/ {
     pub video_id: Option<String>,
 }
 
+
+#[derive(Debug, Clone, FromRow)]
+pub struct SurchargeLancement {
+    pub chemin: String,
+    pub emulateur: Option<String>,
+    pub arguments: Option<String>,
+    pub environnement: Option<String>,
+    pub repertoire: Option<String>,
+    pub crochets: Option<String>,
+}
+
+impl SurchargeLancement {
+    pub fn vide(chemin: String) -> Self {
+        SurchargeLancement {
+            chemin,
+            emulateur: None,
+            arguments: None,
+            environnement: None,
+            repertoire: None,
+            crochets: None,
+        }
+    }
+
+    pub fn est_vide(&self) -> bool {
+        self.emulateur.is_none()
+            && self.arguments.is_none()
+            && self.environnement.is_none()
+            && self.repertoire.is_none()
+            && self.crochets.is_none()
+    }
+
+    pub fn variables(&self) -> Vec<(String, String)> {
+        let mut variables: Vec<(String, String)> = vec![];
+
+        for ligne in self.environnement.clone().unwrap_or_default().lines() {
+            match ligne.split_once('=') {
+                Some((cle, valeur)) if !cle.trim().is_empty() =>
+                    variables.push((String::from(cle.trim()), String::from(valeur))),
+                _ => {},
+            }
+        }
+
+        variables
+    }
+
+    pub fn crochets(&self) -> Crochets {
+        match &self.crochets {
+            Some(json) => match serde_json::from_str(json) {
+                Ok(valeur) => valeur,
+                Err(erreur) => {
+                    println!("ATTENTION: Crochets de {} invalides: {}", self.chemin, erreur);
+                    Crochets::default()
+                },
+            },
+            None => Crochets::default(),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use gtk::gio;
+use gtk::prelude::*;
+
+use crate::donnees::objet::Jeu;
+use crate::gui::jeu::objet::JeuObjet;
+use crate::gui::jeu::vue;
+
+// Grille de tout le catalogue, tenue à jour pendant le scan en arrière-plan.
+pub struct Catalogue {
+    pub fenetre: gtk::ScrolledWindow,
+    modele: gio::ListStore,
+    // Position de chaque jeu dans le modèle, par chemin.
+    positions: RefCell<HashMap<String, u32>>,
+}
+
+impl Catalogue {
+    pub fn new(jeux: Vec<Jeu>, runtime: &tokio::runtime::Handle) -> Self {
+        let modele = vue::modele(&jeux);
+        let positions = jeux
+            .iter()
+            .enumerate()
+            .map(|(position, jeu)| (jeu.chemin.clone(), position as u32))
+            .collect();
+
+        let fenetre = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .vscrollbar_policy(gtk::PolicyType::Automatic)
+            .vexpand(true)
+            .child(&vue::grille(&modele, runtime))
+            .build();
+
+        Catalogue { fenetre, modele, positions: RefCell::new(positions) }
+    }
+
+    // Remplace l'entrée d'un jeu déjà affiché, à sa place, ou l'ajoute à la fin.
+    pub fn ajouter(&self, jeu: Jeu) {
+        let objet = JeuObjet::new(&jeu);
+        let mut positions = self.positions.borrow_mut();
+
+        match positions.get(&jeu.chemin) {
+            Some(position) => self.modele.splice(*position, 1, &[objet]),
+            None => {
+                positions.insert(jeu.chemin, self.modele.n_items());
+                self.modele.append(&objet);
+            },
+        }
+    }
+
+    // Les jeux suivants remontent d'un cran.
+    pub fn retirer(&self, chemin: &str) {
+        let mut positions = self.positions.borrow_mut();
+
+        if let Some(retiree) = positions.remove(chemin) {
+            self.modele.remove(retiree);
+
+            for position in positions.values_mut().filter(|position| **position > retiree) {
+                *position -= 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::PathBuf;
+
+use gtk::glib;
+use gtk::prelude::*;
+
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{Jeu, JeuIGDB};
+use crate::outils::images_perso::{image_perso, TypeImage};
+use crate::outils::fournisseurs;
+use crate::outils::medias::{galerie, SourceMedia, TailleImage};
+
+fn attente() -> gtk::Spinner {
+    gtk::Spinner::builder()
+        .spinning(true)
+        .halign(gtk::Align::Center)
+        .valign(gtk::Align::Center)
+        .build()
+}
+
+// La fenêtre s'ouvre tout de suite; l'image HD est téléchargée sur le runtime.
+fn afficher_image(parent: Option<&gtk::Window>, media: &SourceMedia) {
+    let fenetre = gtk::Window::builder()
+        .default_width(1280)
+        .default_height(720)
+        .child(&attente())
+        .build();
+    fenetre.set_transient_for(parent);
+
+    fenetre.present();
+
+    let media = media.clone();
+    let runtime = tokio::runtime::Handle::current();
+
+    glib::MainContext::default().spawn_local(glib::clone!(@weak fenetre => async move {
+        let chemin = runtime.spawn(async move { media.obtenir(TailleImage::HD).await }).await;
+
+        match chemin {
+            Ok(Some(chemin)) => {
+                let image = gtk::Picture::for_filename(chemin);
+                image.set_content_fit(gtk::ContentFit::Contain);
+                fenetre.set_child(Some(&image));
+            },
+            _ => fenetre.set_child(Some(&gtk::Label::new(Some("Image indisponible.")))),
+        }
+    }));
+}
+
+// Nom du jeu, illustration personnalisée et captures, lus et téléchargés hors de GTK.
+async fn charger_galerie(chemin: String) -> Option<(String, Option<PathBuf>, Vec<(SourceMedia, PathBuf)>)> {
+    let jeu = match Jeu::charger(chemin.clone()).await {
+        Ok(Some(valeur)) => valeur,
+        _ => {
+            println!("ATTENTION: {} n'est pas dans le catalogue.", chemin);
+            return None;
+        },
+    };
+
+    let jeu_igdb = match jeu.jeu {
+        Some(id) => JeuIGDB::charger(id).await.ok().flatten(),
+        None => None,
+    };
+
+    let images = match &jeu_igdb {
+        Some(valeur) => galerie(fournisseurs::medias_jeu(valeur, &chemin).await, TailleImage::CaptureGrande).await,
+        None => vec![],
+    };
+    let perso = image_perso(&chemin, TypeImage::Illustration).await;
+
+    Some((jeu.nom, perso, images))
+}
+
+fn remplir(grille: &gtk::FlowBox, perso: Option<PathBuf>, images: Vec<(SourceMedia, PathBuf)>) {
+    if let Some(image) = &perso {
+        let vue = gtk::Picture::for_filename(image);
+        vue.set_can_shrink(true);
+        vue.set_content_fit(gtk::ContentFit::Contain);
+        vue.set_size_request(320, 180);
+        grille.insert(&vue, -1);
+    }
+
+    for (media, image) in &images {
+        let vue = gtk::Picture::for_filename(image);
+        vue.set_can_shrink(true);
+        vue.set_content_fit(gtk::ContentFit::Contain);
+        vue.set_size_request(320, 180);
+
+        let clic = gtk::GestureClick::new();
+        clic.connect_released(glib::clone!(@strong media => move |geste, _, _, _| {
+            let parent = geste.widget().root().and_downcast::<gtk::Window>();
+            afficher_image(parent.as_ref(), &media);
+        }));
+        vue.add_controller(clic);
+
+        grille.insert(&vue, -1);
+    }
+
+    if images.is_empty() && perso.is_none() {
+        grille.insert(&gtk::Label::new(Some("Aucune image disponible.")), -1);
+    }
+}
+
+pub fn afficher_galerie(parent: Option<&gtk::Window>, chemin: String) {
+    let grille = gtk::FlowBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .column_spacing(10)
+        .row_spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let deroulante = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .child(&attente())
+        .build();
+
+    let fenetre = gtk::Window::builder()
+        .title("Galerie")
+        .default_width(720)
+        .default_height(480)
+        .child(&deroulante)
+        .build();
+    fenetre.set_transient_for(parent);
+
+    fenetre.present();
+
+    let runtime = tokio::runtime::Handle::current();
+
+    glib::MainContext::default().spawn_local(glib::clone!(@weak fenetre, @weak deroulante => async move {
+        match runtime.spawn(charger_galerie(chemin)).await {
+            Ok(Some((nom, perso, images))) => {
+                fenetre.set_title(Some(&format!("Galerie — {}", nom)));
+                remplir(&grille, perso, images);
+                deroulante.set_child(Some(&grille));
+            },
+            _ => fenetre.close(),
+        }
+    }));
+}
@@ -0,0 +1,50 @@
+use gtk::gio;
+use gtk::prelude::*;
+
+use crate::gui::jeu::vue::rafraichir_jeu;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::images_perso::{choisir_image, retirer_image, TypeImage};
+
+pub fn choisir_image_perso(parent: Option<&gtk::Window>, chemin: String, type_image: TypeImage) {
+    let filtre = gtk::FileFilter::new();
+    filtre.set_name(Some("Images"));
+    filtre.add_mime_type("image/png");
+    filtre.add_mime_type("image/jpeg");
+    filtre.add_mime_type("image/webp");
+
+    let titre = match type_image {
+        TypeImage::Couverture => "Choisir une couverture",
+        TypeImage::Illustration => "Choisir une illustration",
+    };
+
+    let dialogue = gtk::FileDialog::builder()
+        .title(titre)
+        .modal(true)
+        .default_filter(&filtre)
+        .build();
+
+    dialogue.open(parent, None::<&gio::Cancellable>, move |resultat| {
+        let source = match resultat.ok().and_then(|fichier| fichier.path()) {
+            Some(valeur) => valeur,
+            None => return,
+        };
+
+        match async_std::task::block_on(choisir_image(&chemin, type_image, &source)) {
+            Ok(image) => {
+                println!("INFO: {} utilisée pour {}.", image.display(), chemin);
+                rafraichir_jeu(&chemin);
+            },
+            Err(erreur) => erreur.afficher_erreur(),
+        }
+    });
+}
+
+pub fn retirer_images_perso(chemin: &str) {
+    for type_image in [TypeImage::Couverture, TypeImage::Illustration] {
+        if let Err(erreur) = async_std::task::block_on(retirer_image(chemin, type_image)) {
+            erreur.afficher_erreur();
+        }
+    }
+
+    rafraichir_jeu(chemin);
+}
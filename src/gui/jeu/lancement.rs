@@ -0,0 +1,30 @@
+use gtk::glib;
+use gtk::prelude::*;
+
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::lanceur::lancer_jeu_async;
+
+// Depuis l'interface: le lancement (et ses crochets) se fait sur le runtime, hors du
+// fil de GTK; un échec est montré dans une fenêtre au-dessus de celle du jeu.
+pub fn lancer_jeu(runtime: &tokio::runtime::Handle, widget: &impl IsA<gtk::Widget>, chemin: String) {
+    let fenetre = widget.root().and_then(|racine| racine.downcast::<gtk::Window>().ok());
+    let tache = runtime.spawn(lancer_jeu_async(chemin.clone()));
+
+    glib::MainContext::default().spawn_local(async move {
+        let detail = match tache.await {
+            Ok(Ok(_)) => return,
+            Ok(Err(erreur)) => {
+                erreur.afficher_erreur();
+                erreur.as_string()
+            },
+            Err(erreur) => erreur.to_string(),
+        };
+
+        gtk::AlertDialog::builder()
+            .modal(true)
+            .message(format!("Impossible de lancer {}", chemin))
+            .detail(detail)
+            .build()
+            .show(fenetre.as_ref());
+    });
+}
@@ -0,0 +1,149 @@
+use gtk::glib;
+use gtk::prelude::*;
+
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{Jeu, SurchargeLancement};
+use crate::gui::jeu::galerie::afficher_galerie;
+use crate::gui::jeu::images::{choisir_image_perso, retirer_images_perso};
+use crate::gui::jeu::options::afficher_options_lancement;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::bureau;
+use crate::outils::favoris::{definir_favori, est_favori};
+use crate::outils::images_perso::TypeImage;
+use crate::gui::jeu::lancement::lancer_jeu;
+use crate::outils::lanceur::{lister_emulateurs, memoriser_emulateur};
+
+fn bouton(libelle: &str) -> gtk::Button {
+    let bouton = gtk::Button::builder()
+        .has_frame(false)
+        .build();
+
+    let etiquette = gtk::Label::builder()
+        .label(libelle)
+        .halign(gtk::Align::Start)
+        .build();
+    bouton.set_child(Some(&etiquette));
+
+    bouton
+}
+
+pub fn afficher_menu(widget: &gtk::Widget, chemin: String, runtime: &tokio::runtime::Handle) {
+    let runtime = runtime.clone();
+    let emulateurs = async_std::task::block_on(lister_emulateurs(&chemin));
+    let choix = match async_std::task::block_on(SurchargeLancement::charger(chemin.clone())) {
+        Ok(Some(surcharge)) => surcharge.emulateur,
+        _ => None,
+    };
+
+    let popover = gtk::Popover::new();
+
+    let boite = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(2)
+        .build();
+
+    let titre = gtk::Label::builder()
+        .label("<b>Lancer avec…</b>")
+        .use_markup(true)
+        .halign(gtk::Align::Start)
+        .margin_start(10)
+        .margin_bottom(5)
+        .build();
+    boite.append(&titre);
+
+    let automatique = bouton(match choix {
+        None => "✓ Automatique",
+        Some(_) => "Automatique",
+    });
+    automatique.connect_clicked(glib::clone!(@weak popover, @weak widget, @strong chemin, @strong runtime => move |_| {
+        popover.popdown();
+        match async_std::task::block_on(memoriser_emulateur(&chemin, None)) {
+            Ok(_) => lancer_jeu(&runtime, &widget, chemin.clone()),
+            Err(erreur) => erreur.afficher_erreur(),
+        }
+    }));
+    boite.append(&automatique);
+
+    for emulateur in emulateurs {
+        let libelle = match choix.as_ref() == Some(&emulateur.nom) {
+            true => format!("✓ {}", emulateur.nom),
+            false => emulateur.nom.clone(),
+        };
+
+        let choisir = bouton(&libelle);
+        choisir.connect_clicked(glib::clone!(@weak popover, @weak widget, @strong chemin, @strong runtime => move |_| {
+            popover.popdown();
+            match async_std::task::block_on(memoriser_emulateur(&chemin, Some(emulateur.nom.clone()))) {
+                Ok(_) => lancer_jeu(&runtime, &widget, chemin.clone()),
+                Err(erreur) => erreur.afficher_erreur(),
+            }
+        }));
+        boite.append(&choisir);
+    }
+
+    boite.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+    let favori = async_std::task::block_on(est_favori(&chemin));
+    let basculer = bouton(match favori {
+        true => "Retirer des favoris",
+        false => "Ajouter aux favoris",
+    });
+    basculer.connect_clicked(glib::clone!(@weak popover, @strong chemin => move |_| {
+        popover.popdown();
+        async_std::task::block_on(async {
+            definir_favori(&chemin, !favori).await;
+            if let Ok(Some(jeu)) = Jeu::charger(chemin.clone()).await {
+                bureau::synchroniser_jeu(&jeu, !favori).await;
+            }
+        });
+    }));
+    boite.append(&basculer);
+
+    let options = bouton("Options de lancement…");
+    options.connect_clicked(glib::clone!(@weak popover, @weak widget, @strong chemin => move |_| {
+        popover.popdown();
+        let parent = widget.root().and_downcast::<gtk::Window>();
+        afficher_options_lancement(parent.as_ref(), chemin.clone());
+    }));
+    boite.append(&options);
+
+    let images = bouton("Galerie…");
+    images.connect_clicked(glib::clone!(@weak popover, @weak widget, @strong chemin => move |_| {
+        popover.popdown();
+        let parent = widget.root().and_downcast::<gtk::Window>();
+        afficher_galerie(parent.as_ref(), chemin.clone());
+    }));
+    boite.append(&images);
+
+    boite.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+
+    for (libelle, type_image) in [
+        ("Choisir une couverture…", TypeImage::Couverture),
+        ("Choisir une illustration…", TypeImage::Illustration),
+    ] {
+        let choisir = bouton(libelle);
+        choisir.connect_clicked(glib::clone!(@weak popover, @weak widget, @strong chemin => move |_| {
+            popover.popdown();
+            let parent = widget.root().and_downcast::<gtk::Window>();
+            choisir_image_perso(parent.as_ref(), chemin.clone(), type_image);
+        }));
+        boite.append(&choisir);
+    }
+
+    let retirer = bouton("Retirer les images personnalisées");
+    retirer.connect_clicked(glib::clone!(@weak popover, @strong chemin => move |_| {
+        popover.popdown();
+        retirer_images_perso(&chemin);
+    }));
+    boite.append(&retirer);
+
+    popover.set_child(Some(&boite));
+    popover.set_parent(widget);
+    popover.connect_closed(|popover| {
+        glib::idle_add_local_once(glib::clone!(@weak popover => move || {
+            popover.unparent();
+        }));
+    });
+
+    popover.popup();
+}
@@ -1,9 +1,20 @@
+use std::cell::{OnceCell, RefCell};
+
 use gtk::glib;
 use gtk::subclass::prelude::*;
 
+use crate::gui::jeu::objet::JeuObjet;
+
 // Object holding the state
 #[derive(Default)]
-pub struct Miniature;
+pub struct Miniature {
+    pub image: OnceCell<gtk::Picture>,
+    pub titre: OnceCell<gtk::Label>,
+    // Jeu affiché; la tuile est recyclée d'un jeu à l'autre par les vues.
+    pub objet: RefCell<Option<JeuObjet>>,
+    // Runtime de l'application: vignettes et lancements y sont exécutés.
+    pub runtime: OnceCell<tokio::runtime::Handle>,
+}
 
 // The central trait for subclassing a GObject
 #[glib::object_subclass]
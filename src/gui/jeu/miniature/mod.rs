@@ -1,12 +1,14 @@
 mod imp;
 
 use glib::Object;
-use gtk::glib;
+use gtk::{gdk, gio, glib};
 use gtk::prelude::*;
+use gtk::subclass::prelude::*;
 
-use crate::chemin::chemins;
-use crate::outils::lanceur::lancer_jeu;
-use crate::donnees::objet::Jeu;
+use crate::gui::jeu::menu::afficher_menu;
+use crate::gui::jeu::objet::JeuObjet;
+use crate::gui::jeu::vignette::{vignette_jeu, HAUTEUR, LARGEUR};
+use crate::gui::jeu::lancement::lancer_jeu;
 
 glib::wrapper! {
     pub struct Miniature(ObjectSubclass<imp::Miniature>)
@@ -15,24 +17,17 @@ glib::wrapper! {
 }
 
 impl Miniature {
-    pub fn modele() -> Self {
-        Miniature::for_jeu(
-            Jeu {
-                jeu: None,
-                chemin: String::new(),
-                nom: String::from("Inconnu"),
-                langue: String::new(),
-            }
-        )
-    }
-
-    pub fn new(nom: String, image: String, commande: String) -> Self {
+    // Tuile vide, remplie par `lier` quand une vue lui attribue un jeu.
+    pub fn new(runtime: &tokio::runtime::Handle) -> Self {
         let miniature: Miniature = Object::builder().build();
+        // Les vues n'espacent pas leurs éléments.
+        miniature.set_margin_start(10);
+        miniature.set_margin_end(10);
 
         let boite = gtk::Box::builder()
             .orientation(gtk::Orientation::Vertical)
-            .width_request(200)
-            .height_request(300)
+            .width_request(LARGEUR)
+            .height_request(HAUTEUR)
             .spacing(5)
             .margin_top(0)
             .margin_bottom(10)
@@ -44,16 +39,16 @@ impl Miniature {
             .vexpand(false)
             .build();
 
-        let image = gtk::Picture::for_filename(image);
+        let image = gtk::Picture::new();
         image.set_can_shrink(true);
         image.set_content_fit(gtk::ContentFit::Cover);
+        image.set_size_request(LARGEUR, HAUTEUR);
         image.set_halign(gtk::Align::Start);
         image.set_hexpand(false);
         image.set_valign(gtk::Align::Start);
         image.set_vexpand(false);
 
         let titre = gtk::Label::builder()
-            .label(nom)
             .wrap(true)
             .wrap_mode(gtk::pango::WrapMode::Word)
             .halign(gtk::Align::Center)
@@ -68,24 +63,86 @@ impl Miniature {
         boite.append(&titre);
 
         let controlleur = gtk::GestureClick::new();
-        controlleur.connect_released(glib::clone!(@strong commande => move |_, _, _, _| {
-            lancer_jeu(commande.clone());
+        controlleur.connect_released(glib::clone!(@weak miniature => move |_, _, _, _| {
+            if let (Some(chemin), Some(runtime)) = (miniature.chemin(), miniature.imp().runtime.get()) {
+                lancer_jeu(runtime, &miniature, chemin);
+            }
+        }));
+
+        let controlleur_options = gtk::GestureClick::new();
+        controlleur_options.set_button(3);
+        controlleur_options.connect_pressed(glib::clone!(@weak miniature => move |geste, _, _, _| {
+            if let (Some(chemin), Some(runtime)) = (miniature.chemin(), miniature.imp().runtime.get()) {
+                afficher_menu(&geste.widget(), chemin, runtime);
+            }
         }));
 
         miniature.add_controller(controlleur);
+        miniature.add_controller(controlleur_options);
         miniature.append(&boite);
 
+        let _ = miniature.imp().image.set(image);
+        let _ = miniature.imp().titre.set(titre);
+        let _ = miniature.imp().runtime.set(runtime.clone());
+
         miniature
     }
 
-    pub fn for_jeu(jeu: Jeu) -> Self {
-        Miniature::new(
-            format!("{} {}", jeu.nom, jeu.langue).trim().to_string(),
-            chemins::trouver_chemin(
-                format!("{}.jpg", jeu.jeu.unwrap_or(0)),
-                chemins::XDG::CACHE,
-            ).unwrap().to_str().unwrap().to_string(),
-            jeu.chemin,
-        )
+    fn chemin(&self) -> Option<String> {
+        self.imp().objet.borrow().as_ref().map(|objet| objet.chemin())
+    }
+
+    pub fn lier(&self, objet: &JeuObjet) {
+        self.imp().objet.replace(Some(objet.clone()));
+
+        if let Some(titre) = self.imp().titre.get() {
+            titre.set_label(&objet.titre());
+        }
+        if let Some(image) = self.imp().image.get() {
+            image.set_paintable(gdk::Paintable::NONE);
+        }
+
+        // La vignette (parfois dessinée) et sa texture sont préparées hors du fil
+        // de l'interface; la tuile a pu être recyclée entre-temps. La vignette lit la
+        // base: elle est calculée sur le runtime, seul le décodage passe par gio.
+        let runtime = match self.imp().runtime.get() {
+            Some(valeur) => valeur.clone(),
+            None => return,
+        };
+
+        glib::MainContext::default().spawn_local(glib::clone!(@weak self as miniature, @strong objet => async move {
+            let chemin = match objet.couverture() {
+                Some(valeur) => valeur,
+                None => {
+                    let jeu = objet.jeu();
+
+                    match runtime.spawn_blocking(move || vignette_jeu(&jeu)).await {
+                        Ok(valeur) if !valeur.is_empty() => {
+                            objet.set_couverture(Some(valeur.clone()));
+                            valeur
+                        },
+                        Ok(_) => return,
+                        Err(erreur) => return println!("ATTENTION: Vignette de {} impossible: {}", objet.chemin(), erreur),
+                    }
+                },
+            };
+
+            let texture = gio::spawn_blocking(move || gdk::Texture::from_filename(chemin).ok())
+                .await
+                .ok()
+                .flatten();
+
+            if miniature.imp().objet.borrow().as_ref() != Some(&objet) {
+                return;
+            }
+
+            if let (Some(image), Some(texture)) = (miniature.imp().image.get(), texture) {
+                image.set_paintable(Some(&texture));
+            }
+        }));
+    }
+
+    pub fn delier(&self) {
+        self.imp().objet.replace(None);
     }
 }
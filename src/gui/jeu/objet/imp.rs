@@ -0,0 +1,43 @@
+use std::cell::{Cell, RefCell};
+
+use gtk::glib;
+use gtk::glib::{ParamSpec, Properties, Value};
+use gtk::prelude::*;
+use gtk::subclass::prelude::*;
+
+#[derive(Properties, Default)]
+#[properties(wrapper_type = super::JeuObjet)]
+pub struct JeuObjet {
+    #[property(get, set)]
+    chemin: RefCell<String>,
+    #[property(get, set)]
+    nom: RefCell<String>,
+    #[property(get, set)]
+    langue: RefCell<String>,
+    // Vignette, calculée la première fois que le jeu est affiché.
+    #[property(get, set, nullable)]
+    couverture: RefCell<Option<String>>,
+    // 0 pour un jeu non identifié.
+    #[property(get, set)]
+    igdb_id: Cell<u32>,
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for JeuObjet {
+    const NAME: &'static str = "JeuObjetLeuriLudotheque";
+    type Type = super::JeuObjet;
+}
+
+impl ObjectImpl for JeuObjet {
+    fn properties() -> &'static [ParamSpec] {
+        Self::derived_properties()
+    }
+
+    fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+        self.derived_set_property(id, value, pspec)
+    }
+
+    fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+        self.derived_property(id, pspec)
+    }
+}
@@ -0,0 +1,35 @@
+mod imp;
+
+use glib::Object;
+use gtk::glib;
+
+use crate::donnees::objet::Jeu;
+
+// Entrée du catalogue vue par les modèles GTK (gio::ListStore).
+glib::wrapper! {
+    pub struct JeuObjet(ObjectSubclass<imp::JeuObjet>);
+}
+
+impl JeuObjet {
+    pub fn new(jeu: &Jeu) -> Self {
+        Object::builder()
+            .property("chemin", &jeu.chemin)
+            .property("nom", &jeu.nom)
+            .property("langue", &jeu.langue)
+            .property("igdb-id", jeu.jeu.unwrap_or(0))
+            .build()
+    }
+
+    pub fn jeu(&self) -> Jeu {
+        Jeu {
+            jeu: Some(self.igdb_id()).filter(|id| *id > 0),
+            chemin: self.chemin(),
+            nom: self.nom(),
+            langue: self.langue(),
+        }
+    }
+
+    pub fn titre(&self) -> String {
+        format!("{} {}", self.nom(), self.langue()).trim().to_string()
+    }
+}
@@ -0,0 +1,187 @@
+use gtk::glib;
+use gtk::prelude::*;
+
+use crate::donnees::config::{obtenir_config, Crochet, Crochets};
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::SurchargeLancement;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::lanceur::tous_emulateurs;
+
+fn optionnel(texte: String) -> Option<String> {
+    match texte.trim().is_empty() {
+        true => None,
+        false => Some(texte),
+    }
+}
+
+fn etiquette(texte: &str) -> gtk::Label {
+    gtk::Label::builder()
+        .label(texte)
+        .halign(gtk::Align::Start)
+        .valign(gtk::Align::Start)
+        .build()
+}
+
+fn texte_tampon(vue: &gtk::TextView) -> String {
+    let tampon = vue.buffer();
+    tampon.text(&tampon.start_iter(), &tampon.end_iter(), false).to_string()
+}
+
+fn vue_crochets(crochets: &[Crochet]) -> gtk::TextView {
+    let vue = gtk::TextView::builder()
+        .monospace(true)
+        .height_request(60)
+        .build();
+
+    let commandes: Vec<String> = crochets.iter().map(|crochet| crochet.commande.clone()).collect();
+    vue.buffer().set_text(&commandes.join("\n"));
+
+    vue
+}
+
+fn lire_crochets(texte: String, anciens: &[Crochet]) -> Vec<Crochet> {
+    texte.lines()
+        .filter(|ligne| !ligne.trim().is_empty())
+        .map(|ligne| Crochet {
+            commande: String::from(ligne),
+            delai: anciens.iter().find(|crochet| crochet.commande == ligne).and_then(|crochet| crochet.delai),
+        })
+        .collect()
+}
+
+pub fn afficher_options_lancement(parent: Option<&gtk::Window>, chemin: String) {
+    let config = obtenir_config();
+
+    let surcharge = match async_std::task::block_on(SurchargeLancement::charger(chemin.clone())) {
+        Ok(Some(valeur)) => valeur,
+        Ok(None) => SurchargeLancement::vide(chemin.clone()),
+        Err(erreur) => return erreur.afficher_erreur(),
+    };
+
+    let noms_emulateurs: Vec<String> = tous_emulateurs(&config)
+        .iter()
+        .map(|emulateur| emulateur.nom.clone())
+        .collect();
+
+    let mut choix: Vec<&str> = vec!["Automatique"];
+    choix.extend(noms_emulateurs.iter().map(|nom| nom.as_str()));
+
+    let emulateur = gtk::DropDown::from_strings(&choix);
+    if let Some(nom) = &surcharge.emulateur {
+        if let Some(position) = noms_emulateurs.iter().position(|valeur| valeur == nom) {
+            emulateur.set_selected(position as u32 + 1);
+        }
+    }
+
+    let arguments = gtk::Entry::builder()
+        .text(surcharge.arguments.clone().unwrap_or(String::new()))
+        .hexpand(true)
+        .build();
+
+    let environnement = gtk::TextView::builder()
+        .monospace(true)
+        .height_request(80)
+        .build();
+    environnement.buffer().set_text(&surcharge.environnement.clone().unwrap_or(String::new()));
+
+    let repertoire = gtk::Entry::builder()
+        .text(surcharge.repertoire.clone().unwrap_or(String::new()))
+        .hexpand(true)
+        .build();
+
+    let crochets = surcharge.crochets();
+    let crochets_avant = vue_crochets(&crochets.avant);
+    let crochets_apres = vue_crochets(&crochets.apres);
+
+    let grille = gtk::Grid::builder()
+        .row_spacing(10)
+        .column_spacing(10)
+        .build();
+
+    grille.attach(&etiquette("Émulateur"), 0, 0, 1, 1);
+    grille.attach(&emulateur, 1, 0, 1, 1);
+    grille.attach(&etiquette("Arguments"), 0, 1, 1, 1);
+    grille.attach(&arguments, 1, 1, 1, 1);
+    grille.attach(&etiquette("Environnement\n(CLE=valeur)"), 0, 2, 1, 1);
+    grille.attach(&environnement, 1, 2, 1, 1);
+    grille.attach(&etiquette("Répertoire"), 0, 3, 1, 1);
+    grille.attach(&repertoire, 1, 3, 1, 1);
+    grille.attach(&etiquette("Crochets avant\n(un par ligne)"), 0, 4, 1, 1);
+    grille.attach(&crochets_avant, 1, 4, 1, 1);
+    grille.attach(&etiquette("Crochets après\n(un par ligne)"), 0, 5, 1, 1);
+    grille.attach(&crochets_apres, 1, 5, 1, 1);
+
+    let annuler = gtk::Button::with_label("Annuler");
+    let enregistrer = gtk::Button::with_label("Enregistrer");
+
+    let boutons = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(10)
+        .halign(gtk::Align::End)
+        .build();
+    boutons.append(&annuler);
+    boutons.append(&enregistrer);
+
+    let boite = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(20)
+        .margin_top(20)
+        .margin_bottom(20)
+        .margin_start(20)
+        .margin_end(20)
+        .build();
+    boite.append(&grille);
+    boite.append(&boutons);
+
+    let fenetre = gtk::Window::builder()
+        .title("Options de lancement")
+        .modal(true)
+        .width_request(500)
+        .child(&boite)
+        .build();
+    fenetre.set_transient_for(parent);
+
+    annuler.connect_clicked(glib::clone!(@weak fenetre => move |_| {
+        fenetre.close();
+    }));
+
+    enregistrer.connect_clicked(glib::clone!(
+        @weak fenetre, @weak emulateur, @weak arguments, @weak environnement, @weak repertoire,
+        @weak crochets_avant, @weak crochets_apres
+        => move |_| {
+            let crochets = Crochets {
+                avant: lire_crochets(texte_tampon(&crochets_avant), &crochets.avant),
+                apres: lire_crochets(texte_tampon(&crochets_apres), &crochets.apres),
+            };
+
+            let surcharge = SurchargeLancement {
+                chemin: chemin.clone(),
+                emulateur: match emulateur.selected() {
+                    0 | gtk::INVALID_LIST_POSITION => None,
+                    position => noms_emulateurs.get(position as usize - 1).cloned(),
+                },
+                arguments: optionnel(arguments.text().to_string()),
+                environnement: optionnel(texte_tampon(&environnement)),
+                repertoire: optionnel(repertoire.text().to_string()),
+                crochets: match crochets.avant.is_empty() && crochets.apres.is_empty() {
+                    true => None,
+                    false => serde_json::to_string(&crochets).ok(),
+                },
+            };
+
+            let resultat = match surcharge.est_vide() {
+                true => async_std::task::block_on(SurchargeLancement::supprimer(chemin.clone())),
+                false => async_std::task::block_on(surcharge.enregistrer()),
+            };
+
+            match resultat {
+                Ok(_) => println!("INFO: Options de lancement de {} enregistrées.", chemin),
+                Err(erreur) => erreur.afficher_erreur(),
+            }
+
+            fenetre.close();
+        }
+    ));
+
+    fenetre.present();
+}
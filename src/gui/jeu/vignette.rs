@@ -0,0 +1,226 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use gtk::{cairo, gdk, gdk_pixbuf};
+
+use crate::chemin::chemins;
+use crate::donnees::igdb::extra::obtenir_plateformes;
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{Jeu, PlateformeIGDB};
+use crate::interne::empreinte::empreinte;
+use crate::outils::fournisseurs::local;
+use crate::outils::images_perso::{image_perso, TypeImage};
+use crate::outils::lanceur::lister_emulateurs;
+use crate::outils::medias::couverture_locale;
+
+pub const LARGEUR: i32 = 200;
+pub const HAUTEUR: i32 = 300;
+
+static ECRITURES: AtomicU64 = AtomicU64::new(0);
+
+fn plus_recent(fichier: &Path, source: &Path) -> bool {
+    let date = |chemin: &Path| std::fs::metadata(chemin).and_then(|meta| meta.modified()).ok();
+
+    match (date(fichier), date(source)) {
+        (Some(fichier), Some(source)) => fichier >= source,
+        _ => false,
+    }
+}
+
+// Plusieurs tuiles peuvent préparer la même vignette en même temps: chacune écrit
+// son propre fichier temporaire, renommé ensuite, pour ne jamais en lire une à moitié
+// écrite.
+fn enregistrer_png(pixbuf: &gdk_pixbuf::Pixbuf, destination: &Path) -> bool {
+    let temporaire = destination.with_file_name(format!(
+        ".{}.{}-{}.part",
+        destination.file_name().map(|nom| nom.to_string_lossy().to_string()).unwrap_or_default(),
+        std::process::id(),
+        ECRITURES.fetch_add(1, Ordering::Relaxed),
+    ));
+
+    let resultat = pixbuf.savev(&temporaire, "png", &[])
+        .map_err(|erreur| erreur.to_string())
+        .and_then(|_| std::fs::rename(&temporaire, destination).map_err(|erreur| erreur.to_string()));
+
+    match resultat {
+        Ok(_) => true,
+        Err(erreur) => {
+            let _ = std::fs::remove_file(&temporaire);
+            println!("ATTENTION: Impossible d'écrire {}: {}", destination.display(), erreur);
+            false
+        },
+    }
+}
+
+// Réduit une image à la taille des tuiles, une seule fois.
+pub fn miniature(source: &Path, nom: String) -> Option<PathBuf> {
+    let destination = match chemins::determiner_chemin(nom, chemins::XDG::CACHE) {
+        Ok(valeur) => valeur,
+        Err(_) => return None,
+    };
+
+    if plus_recent(&destination, source) {
+        return Some(destination);
+    }
+
+    let pixbuf = match gdk_pixbuf::Pixbuf::from_file_at_scale(source, LARGEUR, HAUTEUR, true) {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            println!("ATTENTION: Impossible de lire {}: {}", source.display(), erreur);
+            return None;
+        },
+    };
+
+    match enregistrer_png(&pixbuf, &destination) {
+        true => Some(destination),
+        false => None,
+    }
+}
+
+fn couleur(graine: u64) -> (f64, f64, f64) {
+    let teinte = (graine % 360) as f64 / 60.0;
+    let (saturation, luminosite) = (0.45, 0.35);
+
+    let c = luminosite * saturation;
+    let x = c * (1.0 - (teinte % 2.0 - 1.0).abs());
+    let m = luminosite - c;
+
+    let (r, v, b) = match teinte as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    (r + m, v + m, b + m)
+}
+
+fn couper_lignes(contexte: &cairo::Context, texte: &str, largeur: f64) -> Vec<String> {
+    let mut lignes: Vec<String> = vec![];
+    let mut ligne = String::new();
+
+    for mot in texte.split_whitespace() {
+        let essai = match ligne.is_empty() {
+            true => String::from(mot),
+            false => format!("{} {}", ligne, mot),
+        };
+
+        let trop_large = contexte.text_extents(&essai).is_ok_and(|mesure| mesure.width() > largeur);
+
+        if trop_large && !ligne.is_empty() {
+            lignes.push(ligne);
+            ligne = String::from(mot);
+        } else {
+            ligne = essai;
+        }
+    }
+
+    if !ligne.is_empty() {
+        lignes.push(ligne);
+    }
+
+    lignes
+}
+
+fn centrer(contexte: &cairo::Context, texte: &str, y: f64) {
+    if let Ok(mesure) = contexte.text_extents(texte) {
+        contexte.move_to((LARGEUR as f64 - mesure.width()) / 2.0 - mesure.x_bearing(), y);
+        let _ = contexte.show_text(texte);
+    }
+}
+
+fn dessiner_substitut(nom: &str, plateforme: &str, destination: &Path) -> Option<()> {
+    let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, LARGEUR, HAUTEUR).ok()?;
+    let contexte = cairo::Context::new(&surface).ok()?;
+
+    let (r, v, b) = couleur(empreinte(nom));
+    contexte.set_source_rgb(r, v, b);
+    contexte.paint().ok()?;
+
+    contexte.set_source_rgba(1.0, 1.0, 1.0, 0.9);
+    contexte.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+    contexte.set_font_size(22.0);
+
+    let lignes = couper_lignes(&contexte, nom, LARGEUR as f64 - 30.0);
+    let hauteur_ligne = 28.0;
+    let debut = (HAUTEUR as f64 - hauteur_ligne * lignes.len() as f64) / 2.0 + 20.0;
+
+    for (indice, ligne) in lignes.iter().enumerate() {
+        centrer(&contexte, ligne, debut + hauteur_ligne * indice as f64);
+    }
+
+    contexte.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Normal);
+    contexte.set_font_size(14.0);
+    contexte.set_source_rgba(1.0, 1.0, 1.0, 0.7);
+    centrer(&contexte, plateforme, HAUTEUR as f64 - 20.0);
+
+    drop(contexte);
+    surface.flush();
+
+    let pixbuf = gdk::pixbuf_get_from_surface(&surface, 0, 0, LARGEUR, HAUTEUR)?;
+
+    enregistrer_png(&pixbuf, destination).then_some(())
+}
+
+async fn libelle_plateforme(jeu: &Jeu) -> String {
+    let mut plateformes = match jeu.jeu {
+        Some(id) if id > 0 => obtenir_plateformes(id).await,
+        _ => vec![],
+    };
+
+    if plateformes.is_empty() {
+        if let Some(emulateur) = lister_emulateurs(&jeu.chemin).await.first() {
+            plateformes = emulateur.plateformes.clone();
+        }
+    }
+
+    for plateforme in plateformes {
+        if let Ok(Some(valeur)) = PlateformeIGDB::charger(plateforme).await {
+            return valeur.name_traduit.unwrap_or(valeur.name);
+        }
+    }
+
+    Path::new(&jeu.chemin)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_uppercase())
+        .unwrap_or_default()
+}
+
+// Couverture générée localement pour les jeux sans image.
+pub fn substitut(jeu: &Jeu) -> Option<PathBuf> {
+    let plateforme = async_std::task::block_on(libelle_plateforme(jeu));
+    let nom = format!("miniatures/substituts/{:016x}.png", empreinte(&format!("{}|{}", jeu.nom, plateforme)));
+
+    let destination = chemins::determiner_chemin(nom, chemins::XDG::CACHE).ok()?;
+
+    if !destination.exists() {
+        dessiner_substitut(&jeu.nom, &plateforme, &destination)?;
+    }
+
+    Some(destination)
+}
+
+// Couverture personnalisée, puis celle du fournisseur, puis substitut généré.
+pub fn vignette_jeu(jeu: &Jeu) -> String {
+    let perso = async_std::task::block_on(image_perso(&jeu.chemin, TypeImage::Couverture))
+        .and_then(|source| {
+            let nom = format!("miniatures/perso/{:016x}.png", empreinte(&source.display().to_string()));
+            miniature(&source, nom)
+        });
+
+    let couverture = || match jeu.jeu {
+        Some(id) if local::est_local(id) => local::couverture(&jeu.chemin)
+            .and_then(|source| miniature(&source, format!("miniatures/{}.png", id))),
+        Some(id) if id > 0 => async_std::task::block_on(couverture_locale(id))
+            .and_then(|source| miniature(&source, format!("miniatures/{}.png", id))),
+        _ => None,
+    };
+
+    perso
+        .or_else(couverture)
+        .or_else(|| substitut(jeu))
+        .map(|chemin| chemin.display().to_string())
+        .unwrap_or(String::new())
+}
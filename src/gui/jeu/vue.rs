@@ -0,0 +1,107 @@
+// Vues virtualisées du catalogue: seules les tuiles visibles existent, et elles
+// sont recyclées d'un jeu à l'autre pendant le défilement.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use gtk::{gio, glib};
+use gtk::prelude::*;
+
+use crate::donnees::objet::Jeu;
+use crate::gui::jeu::miniature::Miniature;
+use crate::gui::jeu::objet::JeuObjet;
+
+thread_local! {
+    // Modèles affichés (rangées de l'accueil, catalogue), pour rafraîchir un jeu partout.
+    static MODELES: RefCell<Vec<glib::WeakRef<gio::ListStore>>> = const { RefCell::new(vec![]) };
+}
+
+pub fn modele(jeux: &[Jeu]) -> gio::ListStore {
+    let modele = gio::ListStore::new(JeuObjet::static_type());
+    let objets: Vec<JeuObjet> = jeux.iter().map(JeuObjet::new).collect();
+    modele.extend_from_slice(&objets);
+
+    MODELES.with(|modeles| {
+        let mut modeles = modeles.borrow_mut();
+        modeles.retain(|autre| autre.upgrade().is_some());
+        modeles.push(modele.downgrade());
+    });
+
+    modele
+}
+
+// Remplace le jeu par un objet neuf dans chaque modèle: ses tuiles recalculent leur vignette.
+pub fn rafraichir_jeu(chemin: &str) {
+    rafraichir_jeux(&HashSet::from([String::from(chemin)]));
+}
+
+// Comme rafraichir_jeu, en un seul parcours de chaque modèle.
+pub fn rafraichir_jeux(chemins: &HashSet<String>) {
+    let modeles: Vec<gio::ListStore> = MODELES.with(|modeles| {
+        modeles.borrow().iter().filter_map(|modele| modele.upgrade()).collect()
+    });
+
+    for modele in modeles {
+        for indice in 0..modele.n_items() {
+            let objet = match modele.item(indice).and_then(|objet| objet.downcast::<JeuObjet>().ok()) {
+                Some(valeur) => valeur,
+                None => continue,
+            };
+
+            if chemins.contains(&objet.chemin()) {
+                modele.splice(indice, 1, &[JeuObjet::new(&objet.jeu())]);
+            }
+        }
+    }
+}
+
+fn element(objet: &glib::Object) -> Option<&gtk::ListItem> {
+    objet.downcast_ref::<gtk::ListItem>()
+}
+
+fn fabrique(runtime: &tokio::runtime::Handle) -> gtk::SignalListItemFactory {
+    let fabrique = gtk::SignalListItemFactory::new();
+    let runtime = runtime.clone();
+
+    fabrique.connect_setup(move |_, objet| {
+        if let Some(element) = element(objet) {
+            element.set_child(Some(&Miniature::new(&runtime)));
+        }
+    });
+
+    fabrique.connect_bind(|_, objet| {
+        let element = match element(objet) {
+            Some(valeur) => valeur,
+            None => return,
+        };
+
+        let miniature = element.child().and_then(|enfant| enfant.downcast::<Miniature>().ok());
+        let jeu = element.item().and_then(|item| item.downcast::<JeuObjet>().ok());
+
+        if let (Some(miniature), Some(jeu)) = (miniature, jeu) {
+            miniature.lier(&jeu);
+        }
+    });
+
+    fabrique.connect_unbind(|_, objet| {
+        if let Some(miniature) = element(objet).and_then(|element| element.child()).and_then(|enfant| enfant.downcast::<Miniature>().ok()) {
+            miniature.delier();
+        }
+    });
+
+    fabrique
+}
+
+// Rangée horizontale, pour l'écran d'accueil.
+pub fn liste(modele: &gio::ListStore, runtime: &tokio::runtime::Handle) -> gtk::ListView {
+    let vue = gtk::ListView::new(Some(gtk::NoSelection::new(Some(modele.clone()))), Some(fabrique(runtime)));
+    vue.set_orientation(gtk::Orientation::Horizontal);
+    vue
+}
+
+// Grille de tout le catalogue.
+pub fn grille(modele: &gio::ListStore, runtime: &tokio::runtime::Handle) -> gtk::GridView {
+    let vue = gtk::GridView::new(Some(gtk::NoSelection::new(Some(modele.clone()))), Some(fabrique(runtime)));
+    vue.set_max_columns(20);
+    vue
+}
@@ -1,10 +1,19 @@
+use std::rc::Rc;
+
 use gtk::prelude::*;
 use gtk::{glib, Application, ApplicationWindow, ScrolledWindow, Box};
 
+pub mod catalogue;
 pub mod jeu;
+pub mod progression;
+pub mod propositions;
 
-use crate::donnees::igdb::extra::obtenir_jeux_par;
+use crate::donnees::config::obtenir_config;
+use crate::donnees::igdb::extra::obtenir_catalogue;
+use crate::gui::catalogue::Catalogue;
 use crate::gui::jeu::construire_categorie;
+use crate::outils::accueil::{jeux_rangee, titre_rangee};
+use crate::outils::arriere_plan::{self, Suivi};
 
 const APP_ID: &str = "org.leuriato.ludotheque";
 
@@ -13,23 +22,46 @@ pub fn lancer_application() -> glib::ExitCode {
         .application_id(APP_ID)
         .build();
 
-    application.connect_activate(construire_ui);
+    // Le runtime de main(): le scan et les téléchargements y tournent pendant que la
+    // fenêtre est ouverte.
+    let runtime = tokio::runtime::Handle::current();
+
+    application.connect_activate(move |application| {
+        construire_ui(application, &runtime);
+    });
 
-    application.run()
+    // Les arguments sont ceux de la ligne de commande de la ludothèque, pas de GTK.
+    application.run_with_args::<&str>(&[])
 }
 
-pub fn construire_ui(application: &Application) {
+pub fn construire_ui(application: &Application, runtime: &tokio::runtime::Handle) {
     let deroulante = ScrolledWindow::builder()
         .vscrollbar_policy(gtk::PolicyType::Automatic)
         .hscrollbar_policy(gtk::PolicyType::Never)
         .build();
 
+    let pages = gtk::Stack::builder()
+        .vexpand(true)
+        .build();
+
+    let selecteur = gtk::StackSwitcher::builder()
+        .stack(&pages)
+        .halign(gtk::Align::Center)
+        .margin_top(10)
+        .build();
+
+    let contenu = Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .build();
+    contenu.append(&selecteur);
+    contenu.append(&pages);
+
     let fenetre = ApplicationWindow::builder()
         .application(application)
         .title("LeuriLudothèque")
         .width_request(900)
         .height_request(520)
-        .child(&deroulante)
+        .child(&contenu)
         .build();
 
     let boite = Box::builder()
@@ -37,23 +69,31 @@ pub fn construire_ui(application: &Application) {
         .build();
     deroulante.set_child(Some(&boite));
 
-    let categorie1 = construire_categorie(
-        "Pokémon",
-        //obtenir_jeux_par("name LIKE '%Pok_mon%' ORDER BY rating DESC"),
-    );
-    let categorie2 = construire_categorie(
-        "The Legend of Zelda",
-        //obtenir_jeux_par("collection = 106 ORDER BY rating DESC"),
-    );
-    let categorie3 = construire_categorie(
-        "Professeur Layton",
-        //obtenir_jeux_par("collection = 297 ORDER BY rating DESC"),
-    );
-
-    boite.prepend(&categorie3);
-    boite.prepend(&categorie2);
-    boite.prepend(&categorie1);
+    for rangee in obtenir_config().accueil {
+        let jeux = async_std::task::block_on(jeux_rangee(&rangee));
+
+        if !jeux.is_empty() {
+            let titre = async_std::task::block_on(titre_rangee(&rangee));
+            boite.append(&construire_categorie(&titre, jeux, runtime));
+        }
+    }
+
+    // La fenêtre s'ouvre sur le catalogue existant; le scan le complète ensuite.
+    let catalogue = Rc::new(Catalogue::new(async_std::task::block_on(obtenir_catalogue()), runtime));
+
+    pages.add_titled(&deroulante, Some("accueil"), "Accueil");
+    pages.add_titled(&catalogue.fenetre, Some("catalogue"), "Catalogue");
+
+    let (emetteur, recepteur) = tokio::sync::mpsc::unbounded_channel();
+    let suivi = Suivi::new(emetteur);
+
+    let (bandeau, barre) = progression::construire_bandeau(suivi.clone());
+    contenu.prepend(&bandeau);
+    progression::suivre(recepteur, bandeau, barre, catalogue);
+
+    runtime.spawn(arriere_plan::demarrer(suivi));
 
     fenetre.present();
-}
 
+    propositions::proposer_emulateurs(&fenetre);
+}
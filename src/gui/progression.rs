@@ -0,0 +1,72 @@
+use std::rc::Rc;
+
+use gtk::glib;
+use gtk::prelude::*;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::gui::catalogue::Catalogue;
+use crate::gui::jeu::vue;
+use crate::outils::arriere_plan::{Evenement, Suivi};
+
+// Bandeau du scan en arrière-plan: étape en cours, avancement et annulation.
+pub fn construire_bandeau(suivi: Suivi) -> (gtk::Box, gtk::ProgressBar) {
+    let bandeau = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .margin_top(10)
+        .margin_start(20)
+        .margin_end(20)
+        .spacing(10)
+        .build();
+
+    let barre = gtk::ProgressBar::builder()
+        .show_text(true)
+        .text("Démarrage…")
+        .hexpand(true)
+        .valign(gtk::Align::Center)
+        .build();
+
+    let annuler = gtk::Button::with_label("Annuler");
+    annuler.connect_clicked(move |bouton| {
+        suivi.annuler();
+        bouton.set_sensitive(false);
+        bouton.set_label("Annulation…");
+    });
+
+    bandeau.append(&barre);
+    bandeau.append(&annuler);
+
+    (bandeau, barre)
+}
+
+// Applique les événements du scan à l'interface, sur la boucle principale de GTK.
+pub fn suivre(
+    mut recepteur: UnboundedReceiver<Evenement>,
+    bandeau: gtk::Box,
+    barre: gtk::ProgressBar,
+    catalogue: Rc<Catalogue>,
+) {
+    glib::MainContext::default().spawn_local(async move {
+        let mut etape: &'static str = "";
+
+        while let Some(evenement) = recepteur.recv().await {
+            match evenement {
+                Evenement::Etape(nom) => {
+                    etape = nom;
+                    barre.set_text(Some(&format!("{}…", nom)));
+                    barre.pulse();
+                },
+                Evenement::Progression(faits, total) => {
+                    barre.set_text(Some(&format!("{} ({}/{})", etape, faits, total)));
+                    barre.set_fraction(faits as f64 / total.max(1) as f64);
+                },
+                Evenement::Jeu(jeu) => catalogue.ajouter(jeu),
+                Evenement::Retire(chemin) => catalogue.retirer(&chemin),
+                Evenement::Couvertures(chemins) => vue::rafraichir_jeux(&chemins),
+                Evenement::Fin => break,
+            }
+        }
+
+        // Fin du scan, ou tâche interrompue: le canal est fermé.
+        bandeau.set_visible(false);
+    });
+}
@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+use std::io::IsTerminal;
+use std::rc::Rc;
+
+use gtk::glib;
+use gtk::prelude::*;
+
+use crate::outils::detection::{propositions_premier_lancement, retenir_choix, Proposition};
+
+// Émulateurs détectés au premier lancement, proposés quand la ludothèque n'a pas
+// été ouverte depuis un terminal.
+pub fn proposer_emulateurs(parent: &impl IsA<gtk::Window>) {
+    if std::io::stdin().is_terminal() {
+        return;
+    }
+
+    let propositions = propositions_premier_lancement();
+
+    if propositions.is_empty() {
+        return;
+    }
+
+    let liste = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(5)
+        .build();
+
+    let cases: Vec<gtk::CheckButton> = propositions
+        .iter()
+        .map(|proposition| {
+            let case = gtk::CheckButton::builder()
+                .label(proposition.description())
+                .active(true)
+                .build();
+            liste.append(&case);
+            case
+        })
+        .collect();
+
+    let ignorer = gtk::Button::with_label("Ne plus proposer");
+    let ajouter = gtk::Button::with_label("Ajouter");
+
+    let boutons = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(10)
+        .halign(gtk::Align::End)
+        .build();
+    boutons.append(&ignorer);
+    boutons.append(&ajouter);
+
+    let boite = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(20)
+        .margin_top(20)
+        .margin_bottom(20)
+        .margin_start(20)
+        .margin_end(20)
+        .build();
+    boite.append(&gtk::Label::new(Some("Émulateurs détectés sur ce système:")));
+    boite.append(&liste);
+    boite.append(&boutons);
+
+    let fenetre = gtk::Window::builder()
+        .title("Émulateurs détectés")
+        .modal(true)
+        .width_request(450)
+        .child(&boite)
+        .build();
+    fenetre.set_transient_for(Some(parent));
+
+    // Les propositions ne sont consommées qu'une fois, par l'un des deux boutons.
+    let propositions: Rc<RefCell<Vec<Proposition>>> = Rc::new(RefCell::new(propositions));
+
+    ignorer.connect_clicked(glib::clone!(@weak fenetre, @strong propositions => move |_| {
+        retenir_choix(propositions.take().into_iter().map(|proposition| (proposition, false)).collect());
+        fenetre.close();
+    }));
+
+    ajouter.connect_clicked(glib::clone!(@weak fenetre, @strong propositions => move |_| {
+        retenir_choix(
+            propositions.take()
+                .into_iter()
+                .zip(cases.iter().map(|case| case.is_active()))
+                .collect()
+        );
+        fenetre.close();
+    }));
+
+    fenetre.present();
+}
@@ -0,0 +1,80 @@
+// Conversions entre dates civiles (calendrier grégorien, UTC) et horodatages Unix,
+// comme les first_release_date d'IGDB.
+
+const SECONDES_PAR_JOUR: i64 = 24 * 60 * 60;
+
+// Jours depuis le 1970-01-01 (algorithme de Howard Hinnant).
+fn jours_depuis_epoque(annee: i64, mois: u32, jour: u32) -> i64 {
+    let annee = if mois <= 2 { annee - 1 } else { annee };
+    let ere = annee.div_euclid(400);
+    let annee_ere = annee - ere * 400;
+    let mois_decale = (mois as i64 + 9) % 12;
+    let jour_annee = (153 * mois_decale + 2) / 5 + jour as i64 - 1;
+    let jour_ere = annee_ere * 365 + annee_ere / 4 - annee_ere / 100 + jour_annee;
+
+    ere * 146097 + jour_ere - 719468
+}
+
+pub fn horodatage(annee: i64, mois: u32, jour: u32) -> Option<i64> {
+    if !(1..=12).contains(&mois) || !(1..=31).contains(&jour) {
+        return None;
+    }
+
+    Some(jours_depuis_epoque(annee, mois, jour) * SECONDES_PAR_JOUR)
+}
+
+pub fn date_civile(horodatage: i64) -> (i64, u32, u32) {
+    let jours = horodatage.div_euclid(SECONDES_PAR_JOUR) + 719468;
+    let ere = jours.div_euclid(146097);
+    let jour_ere = jours - ere * 146097;
+    let annee_ere = (jour_ere - jour_ere / 1460 + jour_ere / 36524 - jour_ere / 146096) / 365;
+    let jour_annee = jour_ere - (365 * annee_ere + annee_ere / 4 - annee_ere / 100);
+    let mois_decale = (5 * jour_annee + 2) / 153;
+    let jour = (jour_annee - (153 * mois_decale + 2) / 5 + 1) as u32;
+    let mois = if mois_decale < 10 { mois_decale + 3 } else { mois_decale - 9 } as u32;
+    let annee = annee_ere + ere * 400 + if mois <= 2 { 1 } else { 0 };
+
+    (annee, mois, jour)
+}
+
+// Accepte AAAAMMJJ, AAAA-MM-JJ et leurs variantes horodatées (19910101T000000,
+// 1991-11-21T00:00:00-05:00), ou une année seule.
+pub fn analyser_date(texte: &str) -> Option<i64> {
+    let chiffres: String = texte.trim()
+        .split('T')
+        .next()?
+        .chars()
+        .filter(|caractere| *caractere != '-')
+        .collect();
+
+    if !chiffres.chars().all(|caractere| caractere.is_ascii_digit()) {
+        return None;
+    }
+
+    match chiffres.len() {
+        4 => horodatage(chiffres.parse().ok()?, 1, 1),
+        8 => horodatage(chiffres[0..4].parse().ok()?, chiffres[4..6].parse().ok()?, chiffres[6..8].parse().ok()?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aller_retour() {
+        assert_eq!(horodatage(1970, 1, 1), Some(0));
+        assert_eq!(horodatage(1991, 11, 21), Some(690681600));
+        assert_eq!(date_civile(690681600), (1991, 11, 21));
+        assert_eq!(date_civile(951782400), (2000, 2, 29));
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(analyser_date("19911121T000000"), Some(690681600));
+        assert_eq!(analyser_date("1991-11-21T00:00:00-05:00"), Some(690681600));
+        assert_eq!(analyser_date("1991"), horodatage(1991, 1, 1));
+        assert_eq!(analyser_date("inconnue"), None);
+    }
+}
@@ -0,0 +1,12 @@
+// Empreinte FNV-1a 64 bits, stable d'une version à l'autre (contrairement au
+// hacheur par défaut de la bibliothèque standard), pour nommer les fichiers en cache.
+pub fn empreinte(texte: &str) -> u64 {
+    let mut valeur: u64 = 0xcbf29ce484222325;
+
+    for octet in texte.bytes() {
+        valeur ^= octet as u64;
+        valeur = valeur.wrapping_mul(0x100000001b3);
+    }
+
+    valeur
+}
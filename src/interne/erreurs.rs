@@ -21,6 +21,22 @@ pub trait TraitErreur {
     }
 }
 
+// Pour les traitements par lots qui continuent après un échec (un fichier, un
+// système, un lanceur): les échecs sont affichés au fil de l'eau et le dernier est
+// rendu à l'appelant, pour que la commande se termine en erreur.
+pub fn dernier_echec<E: TraitErreur>(mut echecs: Vec<E>) -> Result<(), E> {
+    let dernier = match echecs.pop() {
+        Some(valeur) => valeur,
+        None => return Ok(()),
+    };
+
+    for echec in echecs {
+        echec.afficher_erreur();
+    }
+
+    Err(dernier)
+}
+
 impl std::fmt::Display for dyn TraitErreur {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_string())
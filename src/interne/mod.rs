@@ -1 +1,3 @@
+pub mod date;
+pub mod empreinte;
 pub mod erreurs;
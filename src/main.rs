@@ -1,24 +1,32 @@
+// Conventions du dépôt: as_err consomme l'erreur, noms IGDB et XDG, variantes Erreur*.
+#![allow(clippy::wrong_self_convention, clippy::upper_case_acronyms, clippy::enum_variant_names)]
+
 mod api;
 mod chemin;
+mod cli;
 mod donnees;
 mod gui;
 mod interne;
 mod outils;
 
-use outils::{dl::telecharger_couvertures, scan::scanner};
+use std::process::ExitCode;
+
+use clap::Parser;
 
 use crate::interne::erreurs::TraitErreur;
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
+    // Avant la base, pour que --help et les erreurs d'usage n'y touchent pas.
+    let arguments = cli::Arguments::parse();
+
     match donnees::igdb::creer_db().await {
         Ok(_) => (),
-        Err(erreur) => return erreur.afficher_erreur(),
+        Err(erreur) => {
+            erreur.afficher_erreur();
+            return ExitCode::FAILURE;
+        },
     }
 
-    scanner().await;
-
-    telecharger_couvertures().await;
-
-    gui::lancer_application();
+    cli::executer(arguments).await
 }
@@ -0,0 +1,224 @@
+// Rangées de l'écran d'accueil: chacune est une requête sur le catalogue, décrite
+// dans la configuration (source, ordre, nombre de jeux).
+
+use crate::donnees::config::{self, OrdreRangee, Rangee, SourceRangee};
+use crate::donnees::igdb::obtenir_db;
+use crate::donnees::objet::Jeu;
+use crate::interne::erreurs::TraitErreur;
+
+// Table d'un groupe de jeux et jointure depuis `jeux`.
+fn groupe(source: &SourceRangee) -> Option<(&'static str, &'static str, &str)> {
+    match source {
+        SourceRangee::Collection(valeur) => Some(("collections", "JOIN collections ON jeux.collection = collections.id", valeur)),
+        SourceRangee::Franchise(valeur) => Some(("franchises", "JOIN franchises ON jeux.franchise = franchises.id", valeur)),
+        SourceRangee::Genre(valeur) => Some((
+            "genres",
+            "JOIN jeux_genres ON jeux_genres.jeu = jeux.id JOIN genres ON jeux_genres.genre = genres.id",
+            valeur,
+        )),
+        SourceRangee::Plateforme(valeur) => Some((
+            "plateformes",
+            "JOIN jeux_plateformes ON jeux_plateformes.jeu = jeux.id JOIN plateformes ON jeux_plateformes.plateforme = plateformes.id",
+            valeur,
+        )),
+        _ => None,
+    }
+}
+
+fn condition_groupe(table: &str) -> String {
+    format!(
+        "({0}.slug = ? OR lower({0}.name) = lower(?) OR lower({0}.name_traduit) = lower(?))",
+        table,
+    )
+}
+
+// Requête de la rangée et valeur du groupe à lier (trois fois) s'il y en a un.
+fn requete(rangee: &Rangee) -> (String, Option<String>) {
+    let mut sql = String::from("SELECT catalogue.* FROM catalogue LEFT JOIN jeux ON catalogue.jeu = jeux.id");
+    let mut valeur: Option<String> = None;
+
+    let naturel = match &rangee.source {
+        SourceRangee::RecemmentAjoutes => "catalogue.ajoute_le DESC",
+        SourceRangee::RecemmentJoues => {
+            sql.push_str(" JOIN parties ON parties.chemin = catalogue.chemin");
+            "parties.jouee_le DESC"
+        },
+        SourceRangee::Favoris => {
+            sql.push_str(" JOIN favoris ON favoris.chemin = catalogue.chemin");
+            "favoris.ajoute_le DESC"
+        },
+        source => match groupe(source) {
+            Some((table, jointure, groupe)) => {
+                sql.push_str(&format!(" {} WHERE {}", jointure, condition_groupe(table)));
+                valeur = Some(String::from(groupe));
+
+                match source {
+                    SourceRangee::Collection(_) | SourceRangee::Franchise(_) => "jeux.first_release_date",
+                    _ => "lower(catalogue.nom)",
+                }
+            },
+            None => "catalogue.ajoute_le DESC",
+        },
+    };
+
+    let ordre = match rangee.ordre {
+        OrdreRangee::Naturel => naturel,
+        OrdreRangee::Nom => "lower(catalogue.nom)",
+        OrdreRangee::Note => "jeux.rating DESC",
+        OrdreRangee::Sortie => "jeux.first_release_date DESC",
+        OrdreRangee::Aleatoire => "random()",
+    };
+
+    sql.push_str(&format!(" ORDER BY {} LIMIT {};", ordre, rangee.maximum));
+
+    (sql, valeur)
+}
+
+pub async fn jeux_rangee(rangee: &Rangee) -> Vec<Jeu> {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_attention();
+            return vec![];
+        },
+    };
+
+    let (sql, valeur) = requete(rangee);
+    let mut requete = sqlx::query_as::<_, Jeu>(&sql);
+
+    if let Some(valeur) = valeur {
+        requete = requete.bind(valeur.clone()).bind(valeur.clone()).bind(valeur);
+    }
+
+    match requete.fetch_all(&db).await {
+        Ok(liste) => liste,
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            vec![]
+        },
+    }
+}
+
+// Titre configuré, sinon celui de la source (nom traduit du groupe s'il est connu).
+pub async fn titre_rangee(rangee: &Rangee) -> String {
+    if let Some(titre) = &rangee.titre {
+        return titre.clone();
+    }
+
+    let (table, valeur) = match &rangee.source {
+        SourceRangee::RecemmentAjoutes => return String::from("Récemment ajoutés"),
+        SourceRangee::RecemmentJoues => return String::from("Récemment joués"),
+        SourceRangee::Favoris => return String::from("Favoris"),
+        source => match groupe(source) {
+            Some((table, _, valeur)) => (table, valeur),
+            None => return String::new(),
+        },
+    };
+
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(_) => return String::from(valeur),
+    };
+
+    sqlx::query_scalar::<_, String>(&format!(
+        "SELECT coalesce(name_traduit, name) FROM {} WHERE {} LIMIT 1;",
+        table,
+        condition_groupe(table),
+    ))
+        .bind(valeur)
+        .bind(valeur)
+        .bind(valeur)
+        .fetch_optional(&db).await
+        .ok()
+        .flatten()
+        .unwrap_or(String::from(valeur))
+}
+
+fn decrire(rangee: &Rangee) -> String {
+    let source = match &rangee.source {
+        SourceRangee::RecemmentAjoutes => String::from("récemment ajoutés"),
+        SourceRangee::RecemmentJoues => String::from("récemment joués"),
+        SourceRangee::Favoris => String::from("favoris"),
+        SourceRangee::Collection(valeur) => format!("collection {}", valeur),
+        SourceRangee::Franchise(valeur) => format!("franchise {}", valeur),
+        SourceRangee::Genre(valeur) => format!("genre {}", valeur),
+        SourceRangee::Plateforme(valeur) => format!("plateforme {}", valeur),
+    };
+
+    match &rangee.titre {
+        Some(titre) => format!("« {} »: {}, ordre {:?}, {} jeux au plus", titre, source, rangee.ordre, rangee.maximum),
+        None => format!("{}, ordre {:?}, {} jeux au plus", source, rangee.ordre, rangee.maximum),
+    }
+}
+
+pub fn afficher() {
+    for (indice, rangee) in config::obtenir_config().accueil.iter().enumerate() {
+        println!("{}. {}", indice + 1, decrire(rangee));
+    }
+}
+
+// Modifie les rangées de la configuration, puis les affiche.
+pub fn modifier(modification: impl FnOnce(&mut Vec<Rangee>) -> Result<(), String>) -> Result<(), String> {
+    let mut configuration = config::obtenir_config();
+
+    modification(&mut configuration.accueil)?;
+
+    match config::enregistrer_config(configuration) {
+        Ok(_) => {
+            afficher();
+            Ok(())
+        },
+        Err(erreur) => Err(erreur.as_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requete_collection() {
+        let rangee = Rangee {
+            titre: None,
+            source: SourceRangee::Collection(String::from("pokemon")),
+            ordre: OrdreRangee::Naturel,
+            maximum: 12,
+        };
+
+        let (sql, valeur) = requete(&rangee);
+
+        assert!(sql.contains("JOIN collections ON jeux.collection = collections.id WHERE (collections.slug = ?"));
+        assert!(sql.ends_with("ORDER BY jeux.first_release_date LIMIT 12;"));
+        assert_eq!(valeur.as_deref(), Some("pokemon"));
+    }
+
+    #[test]
+    fn requete_favoris_par_note() {
+        let rangee = Rangee {
+            titre: None,
+            source: SourceRangee::Favoris,
+            ordre: OrdreRangee::Note,
+            maximum: 5,
+        };
+
+        let (sql, valeur) = requete(&rangee);
+
+        assert!(sql.contains("JOIN favoris ON favoris.chemin = catalogue.chemin"));
+        assert!(sql.ends_with("ORDER BY jeux.rating DESC LIMIT 5;"));
+        assert_eq!(valeur, None);
+    }
+
+    #[test]
+    fn requete_recemment_ajoutes() {
+        let rangee = Rangee {
+            titre: None,
+            source: SourceRangee::RecemmentAjoutes,
+            ordre: OrdreRangee::Naturel,
+            maximum: 20,
+        };
+
+        let (sql, _) = requete(&rangee);
+
+        assert!(sql.ends_with("ORDER BY catalogue.ajoute_le DESC LIMIT 20;"));
+    }
+}
@@ -0,0 +1,106 @@
+// Scan et téléchargements du démarrage, exécutés pendant que l'interface est déjà
+// ouverte sur le catalogue existant. L'avancement remonte par un canal; l'annulation
+// est prise en compte entre deux jeux, deux téléchargements ou deux étapes.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::donnees::objet::Jeu;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::dl::telecharger_couvertures;
+use crate::outils::medias::{self, TypeMedia};
+use crate::outils::{bureau, retroarch, scan, synchro};
+
+#[derive(Debug, Clone)]
+pub enum Evenement {
+    // Début d'une étape, sans avancement connu.
+    Etape(&'static str),
+    // Jeux traités sur le total de l'étape en cours.
+    Progression(usize, usize),
+    // Jeu ajouté ou mis à jour dans le catalogue.
+    Jeu(Jeu),
+    // Chemin d'un jeu retiré du catalogue.
+    Retire(String),
+    // Chemins des jeux dont la couverture vient d'être téléchargée.
+    Couvertures(HashSet<String>),
+    Fin,
+}
+
+// Où signaler l'avancement, et si l'utilisateur a demandé l'arrêt. Sans émetteur,
+// les événements sont ignorés (ligne de commande).
+#[derive(Clone, Default)]
+pub struct Suivi {
+    emetteur: Option<UnboundedSender<Evenement>>,
+    annulation: Arc<AtomicBool>,
+}
+
+impl Suivi {
+    pub fn new(emetteur: UnboundedSender<Evenement>) -> Self {
+        Suivi {
+            emetteur: Some(emetteur),
+            annulation: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn signaler(&self, evenement: Evenement) {
+        if let Some(emetteur) = &self.emetteur {
+            // L'interface a pu être fermée: plus personne n'écoute.
+            let _ = emetteur.send(evenement);
+        }
+    }
+
+    pub fn annuler(&self) {
+        self.annulation.store(true, Ordering::Relaxed);
+    }
+
+    pub fn est_annule(&self) -> bool {
+        self.annulation.load(Ordering::Relaxed)
+    }
+}
+
+// Annonce l'étape, sauf si l'arrêt a été demandé.
+fn commencer(suivi: &Suivi, etape: &'static str) -> bool {
+    if suivi.est_annule() {
+        return false;
+    }
+
+    suivi.signaler(Evenement::Etape(etape));
+    true
+}
+
+pub async fn demarrer(suivi: Suivi) {
+    if commencer(&suivi, "Recherche des jeux") {
+        scan::scanner(&suivi).await;
+    }
+
+    if commencer(&suivi, "Import des listes RetroArch") {
+        retroarch::importer_listes(&suivi).await;
+    }
+
+    if commencer(&suivi, "Mise à jour des fiches IGDB") {
+        synchro::synchroniser_au_demarrage(&suivi).await;
+    }
+
+    if commencer(&suivi, "Téléchargement des couvertures") {
+        telecharger_couvertures(&suivi).await;
+    }
+
+    if commencer(&suivi, "Téléchargement des logos") {
+        medias::telecharger_medias(&[TypeMedia::LogoPlateforme, TypeMedia::LogoEntreprise], &suivi).await;
+    }
+
+    if commencer(&suivi, "Lanceurs du bureau") {
+        if let Err(erreur) = bureau::synchroniser().await {
+            erreur.afficher_attention();
+        }
+    }
+
+    if suivi.est_annule() {
+        println!("INFO: Scan du démarrage interrompu.");
+    }
+
+    suivi.signaler(Evenement::Fin);
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use crate::chemin::json;
+use crate::interne::erreurs::TraitErreur;
+
+// Erreur Ecriture
+pub struct ErreurEcriture {
+    pub erreur: std::io::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurEcriture {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurEcriture(self))
+    }
+}
+
+impl TraitErreur for ErreurEcriture {
+    fn message(&self) -> String {
+        format!("Impossible d'écrire le lanceur {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur Configuration
+pub struct ErreurConfiguration {
+    pub erreur: json::err::Erreur,
+}
+
+impl ErreurConfiguration {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurConfiguration(self))
+    }
+}
+
+impl TraitErreur for ErreurConfiguration {
+    fn message(&self) -> String {
+        String::from("Impossible d'enregistrer le mode des lanceurs de bureau.")
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.as_string())
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurEcriture(ErreurEcriture),
+    ErreurConfiguration(ErreurConfiguration),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurEcriture(erreur) => erreur,
+            Erreur::ErreurConfiguration(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
@@ -0,0 +1,194 @@
+// Lanceurs freedesktop (.desktop) des jeux, pour les retrouver dans la recherche
+// d'applications de GNOME ou KDE. Ils relancent la ludothèque (`ludotheque lancer`)
+// pour que les émulateurs, surcharges et crochets du catalogue s'appliquent.
+
+pub mod err;
+
+use std::path::{Path, PathBuf};
+
+use crate::donnees::config::{self, LanceursBureau};
+use crate::donnees::igdb::extra::obtenir_catalogue;
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{Jeu, JeuIGDB};
+use crate::interne::empreinte::empreinte;
+use crate::interne::erreurs::{dernier_echec, TraitErreur};
+use crate::outils::bureau::err::*;
+use crate::outils::export;
+use crate::outils::favoris::favoris;
+use crate::outils::images_perso::{image_perso, TypeImage};
+
+const PREFIXE: &str = "ludotheque-";
+
+fn dossier_applications() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or(dirs::home_dir().unwrap_or_default().join(".local/share"))
+        .join("applications")
+}
+
+fn nom_entree(chemin: &str) -> String {
+    format!("{}{:016x}.desktop", PREFIXE, empreinte(chemin))
+}
+
+// Échappement des valeurs texte d'un fichier .desktop.
+fn echapper(texte: &str) -> String {
+    texte.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+}
+
+// Argument de la clé Exec: entre guillemets, avec ", `, $ et \ échappés et % doublé,
+// puis échappé comme toute valeur texte.
+fn citer_exec(argument: &str) -> String {
+    let mut cite = String::from("\"");
+
+    for caractere in argument.chars() {
+        match caractere {
+            '"' | '`' | '$' | '\\' => {
+                cite.push('\\');
+                cite.push(caractere);
+            },
+            '%' => cite.push_str("%%"),
+            autre => cite.push(autre),
+        }
+    }
+    cite.push('"');
+
+    echapper(&cite)
+}
+
+fn generer(nom: &str, exe: &Path, chemin: &str, icone: Option<&Path>) -> String {
+    let exe = exe.display().to_string();
+    let mut texte = String::from("[Desktop Entry]\nType=Application\nVersion=1.0\n");
+
+    texte.push_str(&format!("Name={}\n", echapper(nom)));
+    texte.push_str("Comment=Jeu de la ludothèque\n");
+    texte.push_str(&format!("Exec={} lancer {}\n", citer_exec(&exe), citer_exec(chemin)));
+    texte.push_str(&format!("TryExec={}\n", echapper(&exe)));
+
+    if let Some(icone) = icone {
+        texte.push_str(&format!("Icon={}\n", echapper(&icone.display().to_string())));
+    }
+
+    texte.push_str("Terminal=false\nCategories=Game;\n");
+    texte.push_str(&format!("X-Ludotheque-Chemin={}\n", echapper(chemin)));
+
+    texte
+}
+
+pub async fn ecrire_entree(jeu: &Jeu) -> Result<PathBuf, Erreur> {
+    let dossier = dossier_applications();
+    let fichier = dossier.join(nom_entree(&jeu.chemin));
+
+    let igdb = match jeu.jeu {
+        Some(id) if id > 0 => JeuIGDB::charger_traduit(id).await.ok().flatten(),
+        _ => None,
+    };
+
+    let nom = igdb.as_ref().map(|igdb| igdb.name.clone()).unwrap_or(jeu.nom.clone());
+    let icone = match &igdb {
+        Some(igdb) => export::couverture(jeu, igdb).await,
+        None => image_perso(&jeu.chemin, TypeImage::Couverture).await,
+    };
+
+    let exe = std::env::current_exe().unwrap_or(PathBuf::from("ludotheque"));
+
+    if let Err(erreur) = std::fs::create_dir_all(&dossier) {
+        return ErreurEcriture { erreur, chemin: fichier }.as_err();
+    }
+
+    match std::fs::write(&fichier, generer(&nom, &exe, &jeu.chemin, icone.as_deref())) {
+        Ok(_) => Ok(fichier),
+        Err(erreur) => ErreurEcriture { erreur, chemin: fichier }.as_err(),
+    }
+}
+
+pub fn retirer_entree(chemin: &str) {
+    let _ = std::fs::remove_file(dossier_applications().join(nom_entree(chemin)));
+}
+
+async fn jeux_voulus(mode: LanceursBureau) -> Vec<Jeu> {
+    match mode {
+        LanceursBureau::Aucun => vec![],
+        LanceursBureau::Tous => obtenir_catalogue().await,
+        LanceursBureau::Favoris => {
+            let favoris = favoris().await;
+            obtenir_catalogue().await
+                .into_iter()
+                .filter(|jeu| favoris.contains(&jeu.chemin))
+                .collect()
+        },
+    }
+}
+
+// Écrit les lanceurs voulus par la configuration et supprime les autres.
+pub async fn synchroniser() -> Result<(), Erreur> {
+    let jeux = jeux_voulus(config::obtenir_config().lanceurs_bureau).await;
+    let mut voulus: Vec<String> = vec![];
+    let mut echecs: Vec<Erreur> = vec![];
+
+    for jeu in &jeux {
+        match ecrire_entree(jeu).await {
+            Ok(_) => voulus.push(nom_entree(&jeu.chemin)),
+            Err(erreur) => echecs.push(erreur),
+        }
+    }
+
+    let mut retires = 0;
+
+    if let Ok(entrees) = std::fs::read_dir(dossier_applications()) {
+        for entree in entrees.flatten() {
+            let nom = entree.file_name().to_string_lossy().to_string();
+
+            let obsolete = nom.starts_with(PREFIXE) && nom.ends_with(".desktop") && !voulus.contains(&nom);
+
+            if obsolete && std::fs::remove_file(entree.path()).is_ok() {
+                retires += 1;
+            }
+        }
+    }
+
+    if !voulus.is_empty() || retires > 0 {
+        println!("INFO: {} lanceur(s) de jeu dans les applications, {} retiré(s).", voulus.len(), retires);
+    }
+
+    dernier_echec(echecs)
+}
+
+// Après un changement de favori.
+pub async fn synchroniser_jeu(jeu: &Jeu, favori: bool) {
+    match config::obtenir_config().lanceurs_bureau {
+        LanceursBureau::Favoris if favori => {
+            if let Err(erreur) = ecrire_entree(jeu).await {
+                erreur.afficher_attention();
+            }
+        },
+        LanceursBureau::Favoris => retirer_entree(&jeu.chemin),
+        _ => {},
+    }
+}
+
+// Change le mode de la configuration, puis met les lanceurs en conformité.
+pub async fn commande(mode: Option<LanceursBureau>) -> Result<(), Erreur> {
+    if let Some(mode) = mode {
+        let mut configuration = config::obtenir_config();
+        configuration.lanceurs_bureau = mode;
+
+        if let Err(erreur) = config::enregistrer_config(configuration) {
+            return ErreurConfiguration { erreur }.as_err();
+        }
+    }
+
+    synchroniser().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exec() {
+        assert_eq!(citer_exec("/jeux/Zelda (E).sfc"), "\"/jeux/Zelda (E).sfc\"");
+        assert_eq!(citer_exec("/jeux/100% \"$x\".iso"), "\"/jeux/100%% \\\\\"\\\\$x\\\\\".iso\"");
+    }
+}
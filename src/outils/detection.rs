@@ -0,0 +1,327 @@
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::donnees::config::{self, ConfigRetroArch, Crochets, Emulateur, LudothequeConfig, TypeEmulateur};
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::lanceur::citer;
+use crate::outils::retroarch;
+
+struct EmulateurConnu {
+    nom: &'static str,
+    executables: &'static [&'static str],
+    flatpak: &'static str,
+    appimage: &'static str,
+    // {commande} est remplacé par l'exécutable trouvé.
+    modele: &'static str,
+    extensions: &'static [&'static str],
+    plateformes: &'static [u32],
+}
+
+const EMULATEURS_CONNUS: &[EmulateurConnu] = &[
+    EmulateurConnu {
+        nom: "Dolphin",
+        executables: &["dolphin-emu"],
+        flatpak: "org.DolphinEmu.dolphin-emu",
+        appimage: "dolphin",
+        modele: "{commande} -b {arguments} -e {chemin}",
+        extensions: &["iso", "gcm", "gcz", "rvz", "wbfs", "ciso", "wad", "dol"],
+        plateformes: &[21, 5],
+    },
+    EmulateurConnu {
+        nom: "PCSX2",
+        executables: &["pcsx2-qt", "pcsx2"],
+        flatpak: "net.pcsx2.PCSX2",
+        appimage: "pcsx2",
+        modele: "{commande} -batch {arguments} -- {chemin}",
+        extensions: &["iso", "chd", "cso", "gz"],
+        plateformes: &[8],
+    },
+    EmulateurConnu {
+        nom: "PPSSPP",
+        executables: &["PPSSPPSDL", "PPSSPPQt", "ppsspp"],
+        flatpak: "org.ppsspp.PPSSPP",
+        appimage: "ppsspp",
+        modele: "{commande} {arguments} {chemin}",
+        extensions: &["iso", "cso", "pbp"],
+        plateformes: &[38],
+    },
+    EmulateurConnu {
+        nom: "DuckStation",
+        executables: &["duckstation-qt", "duckstation"],
+        flatpak: "org.duckstation.DuckStation",
+        appimage: "duckstation",
+        modele: "{commande} -batch {arguments} -- {chemin}",
+        extensions: &["cue", "chd", "pbp", "m3u", "ecm"],
+        plateformes: &[7],
+    },
+    EmulateurConnu {
+        nom: "mGBA",
+        executables: &["mgba-qt", "mgba"],
+        flatpak: "io.mgba.mGBA",
+        appimage: "mgba",
+        modele: "{commande} {arguments} {chemin}",
+        extensions: &["gba", "gb", "gbc"],
+        plateformes: &[24, 33, 22],
+    },
+    EmulateurConnu {
+        nom: "melonDS",
+        executables: &["melonDS", "melonds"],
+        flatpak: "net.kuribo64.melonDS",
+        appimage: "melonds",
+        modele: "{commande} {arguments} {chemin}",
+        extensions: &["nds"],
+        plateformes: &[20],
+    },
+    EmulateurConnu {
+        nom: "Mupen64Plus",
+        executables: &["mupen64plus"],
+        flatpak: "",
+        appimage: "",
+        modele: "{commande} {arguments} {chemin}",
+        extensions: &["z64", "n64", "v64"],
+        plateformes: &[4],
+    },
+    EmulateurConnu {
+        nom: "Snes9x",
+        executables: &["snes9x-gtk", "snes9x"],
+        flatpak: "com.snes9x.Snes9x",
+        appimage: "snes9x",
+        modele: "{commande} {arguments} {chemin}",
+        extensions: &["sfc", "smc"],
+        plateformes: &[19],
+    },
+    EmulateurConnu {
+        nom: "Flycast",
+        executables: &["flycast"],
+        flatpak: "org.flycast.Flycast",
+        appimage: "flycast",
+        modele: "{commande} {arguments} {chemin}",
+        extensions: &["cdi", "gdi", "chd"],
+        plateformes: &[23],
+    },
+    EmulateurConnu {
+        nom: "Ryujinx",
+        executables: &["Ryujinx", "ryujinx"],
+        flatpak: "org.ryujinx.Ryujinx",
+        appimage: "ryujinx",
+        modele: "{commande} {arguments} {chemin}",
+        extensions: &["nsp", "xci"],
+        plateformes: &[130],
+    },
+];
+
+pub enum Proposition {
+    Emulateur(Emulateur),
+    RetroArch(ConfigRetroArch),
+}
+
+impl Proposition {
+    pub fn nom(&self) -> String {
+        match self {
+            Proposition::Emulateur(emulateur) => emulateur.nom.clone(),
+            Proposition::RetroArch(_) => String::from("RetroArch"),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Proposition::Emulateur(emulateur) =>
+                format!("{} ({})", emulateur.nom, emulateur.commande),
+            Proposition::RetroArch(retroarch) =>
+                format!("RetroArch et ses cœurs ({})", retroarch.commande),
+        }
+    }
+
+    pub fn appliquer(self, config: &mut LudothequeConfig) {
+        match self {
+            Proposition::Emulateur(emulateur) => config.emulateurs.push(emulateur),
+            Proposition::RetroArch(retroarch) => config.retroarch = Some(retroarch),
+        }
+    }
+}
+
+fn chercher_path(executable: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+
+    std::env::split_paths(&path)
+        .map(|dossier| dossier.join(executable))
+        .find(|chemin| chemin.is_file())
+}
+
+fn chercher_flatpak(identifiant: &str) -> Option<String> {
+    if identifiant.is_empty() {
+        return None;
+    }
+
+    let mut exports: Vec<PathBuf> = vec![PathBuf::from("/var/lib/flatpak/exports/bin")];
+    if let Some(dossier) = dirs::data_dir() {
+        exports.push(dossier.join("flatpak/exports/bin"));
+    }
+
+    match exports.iter().any(|dossier| dossier.join(identifiant).exists()) {
+        true => Some(format!("flatpak run {}", identifiant)),
+        false => None,
+    }
+}
+
+fn chercher_appimage(prefixe: &str) -> Option<PathBuf> {
+    if prefixe.is_empty() {
+        return None;
+    }
+
+    let dossiers: Vec<PathBuf> = match dirs::home_dir() {
+        Some(maison) => vec![maison.join("Applications"), maison.join("AppImages"), maison.join(".local/bin")],
+        None => return None,
+    };
+
+    for dossier in dossiers {
+        let entrees = match std::fs::read_dir(&dossier) {
+            Ok(valeur) => valeur,
+            Err(_) => continue,
+        };
+
+        for entree in entrees.filter_map(|entree| entree.ok()) {
+            let nom = entree.file_name().to_string_lossy().to_lowercase();
+            if nom.starts_with(prefixe) && nom.ends_with(".appimage") {
+                return Some(entree.path());
+            }
+        }
+    }
+
+    None
+}
+
+fn chemin_commande(chemin: &Path) -> String {
+    citer(&chemin.display().to_string())
+}
+
+fn trouver_commande(connu: &EmulateurConnu) -> Option<String> {
+    connu.executables.iter()
+        .find_map(|executable| chercher_path(executable))
+        .map(|chemin| chemin_commande(&chemin))
+        .or_else(|| chercher_flatpak(connu.flatpak))
+        .or_else(|| chercher_appimage(connu.appimage).map(|chemin| chemin_commande(&chemin)))
+}
+
+fn trouver_retroarch() -> Option<String> {
+    chercher_path("retroarch")
+        .map(|chemin| chemin_commande(&chemin))
+        .or_else(|| chercher_flatpak("org.libretro.RetroArch"))
+        .or_else(|| chercher_appimage("retroarch").map(|chemin| chemin_commande(&chemin)))
+}
+
+pub fn detecter_emulateurs(config: &LudothequeConfig) -> Vec<Proposition> {
+    let mut propositions: Vec<Proposition> = vec![];
+
+    let refuse = |nom: &str| config.emulateurs_refuses.iter().any(|refus| refus == nom);
+
+    if config.retroarch.is_none() && !refuse("RetroArch") {
+        if let Some(commande) = trouver_retroarch() {
+            propositions.push(Proposition::RetroArch(ConfigRetroArch {
+                commande,
+                repertoire_coeurs: None,
+                repertoire_infos: None,
+                importer_listes: true,
+            }));
+        }
+    }
+
+    for connu in EMULATEURS_CONNUS {
+        if refuse(connu.nom) || config.emulateurs.iter().any(|emulateur| emulateur.nom == connu.nom) {
+            continue;
+        }
+
+        let commande = match trouver_commande(connu) {
+            Some(valeur) => valeur,
+            None => continue,
+        };
+
+        propositions.push(Proposition::Emulateur(Emulateur {
+            nom: String::from(connu.nom),
+            commande: connu.modele.replace("{commande}", &commande),
+            extensions: connu.extensions.iter().map(|extension| String::from(*extension)).collect(),
+            priorite: 0,
+            plateformes: connu.plateformes.to_vec(),
+            type_emulateur: TypeEmulateur::Commande,
+            coeur: None,
+            crochets: Crochets::default(),
+        }));
+    }
+
+    propositions
+}
+
+fn confirmer(question: &str) -> bool {
+    print!("{} [O/n] ", question);
+    let _ = std::io::stdout().flush();
+
+    let mut reponse = String::new();
+    if std::io::stdin().lock().read_line(&mut reponse).is_err() {
+        return false;
+    }
+
+    matches!(reponse.trim().to_lowercase().as_str(), "" | "o" | "oui" | "y" | "yes")
+}
+
+// Émulateurs à proposer lors du premier lancement, hors ceux déjà refusés.
+pub fn propositions_premier_lancement() -> Vec<Proposition> {
+    let config = config::obtenir_config();
+
+    match config.emulateurs.is_empty() {
+        true => detecter_emulateurs(&config),
+        false => vec![],
+    }
+}
+
+// Ajoute les propositions acceptées et retient les refus, pour ne plus les proposer.
+// Les cœurs d'un RetroArch accepté sont ajoutés aussitôt, depuis le terminal comme
+// depuis l'interface.
+pub fn retenir_choix(choix: Vec<(Proposition, bool)>) {
+    let mut config = config::obtenir_config();
+    let mut ajouts = 0;
+
+    for (proposition, accepte) in choix {
+        match accepte {
+            true => {
+                proposition.appliquer(&mut config);
+                ajouts += 1;
+            },
+            false => config.emulateurs_refuses.push(proposition.nom()),
+        }
+    }
+
+    match config::enregistrer_config(config) {
+        Ok(_) if ajouts > 0 => {
+            println!("INFO: {} émulateur(s) ajouté(s) à la configuration.", ajouts);
+            retroarch::synchroniser_coeurs();
+        },
+        Ok(_) => {},
+        Err(erreur) => erreur.afficher_erreur(),
+    }
+}
+
+// Propose les émulateurs détectés lors du premier lancement, depuis un terminal;
+// sinon, l'interface s'en charge.
+pub fn proposer_emulateurs() {
+    if !std::io::stdin().is_terminal() {
+        return;
+    }
+
+    let propositions = propositions_premier_lancement();
+
+    if propositions.is_empty() {
+        return;
+    }
+
+    println!("INFO: Émulateurs détectés:");
+
+    let choix: Vec<(Proposition, bool)> = propositions
+        .into_iter()
+        .map(|proposition| {
+            let accepte = confirmer(&format!("Ajouter {} ?", proposition.description()));
+            (proposition, accepte)
+        })
+        .collect();
+
+    retenir_choix(choix);
+}
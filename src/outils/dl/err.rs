@@ -0,0 +1,138 @@
+use std::path::PathBuf;
+
+use crate::interne::erreurs::TraitErreur;
+
+// Erreur Requete
+pub struct ErreurRequete {
+    pub erreur: reqwest::Error,
+    pub url: String,
+}
+
+impl ErreurRequete {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurRequete(self))
+    }
+}
+
+impl TraitErreur for ErreurRequete {
+    fn message(&self) -> String {
+        format!("La requête vers {} a échoué.", self.url)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur Statut
+pub struct ErreurStatut {
+    pub statut: reqwest::StatusCode,
+    pub url: String,
+}
+
+impl ErreurStatut {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurStatut(self))
+    }
+}
+
+impl TraitErreur for ErreurStatut {
+    fn message(&self) -> String {
+        format!("Le serveur a répondu {} pour {}.", self.statut, self.url)
+    }
+}
+
+// Erreur Contenu
+pub struct ErreurContenu {
+    pub type_contenu: Option<String>,
+    pub url: String,
+}
+
+impl ErreurContenu {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurContenu(self))
+    }
+}
+
+impl TraitErreur for ErreurContenu {
+    fn message(&self) -> String {
+        format!("{} n'est pas une image.", self.url)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("Type de contenu: {}", self.type_contenu.clone().unwrap_or(String::from("inconnu"))))
+    }
+}
+
+// Erreur Fichier
+pub struct ErreurFichier {
+    pub erreur: std::io::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurFichier {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurFichier(self))
+    }
+}
+
+impl TraitErreur for ErreurFichier {
+    fn message(&self) -> String {
+        format!("Impossible d'écrire {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurRequete(ErreurRequete),
+    ErreurStatut(ErreurStatut),
+    ErreurContenu(ErreurContenu),
+    ErreurFichier(ErreurFichier),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurRequete(erreur) => erreur,
+            Erreur::ErreurStatut(erreur) => erreur,
+            Erreur::ErreurContenu(erreur) => erreur,
+            Erreur::ErreurFichier(erreur) => erreur,
+        }
+    }
+
+    // Les erreurs réseau, 429 et 5xx peuvent disparaître en réessayant.
+    pub fn temporaire(&self) -> bool {
+        match self {
+            Erreur::ErreurRequete(_) => true,
+            Erreur::ErreurStatut(erreur) =>
+                erreur.statut == reqwest::StatusCode::TOO_MANY_REQUESTS || erreur.statut.is_server_error(),
+            Erreur::ErreurContenu(_) | Erreur::ErreurFichier(_) => false,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
@@ -0,0 +1,253 @@
+pub mod err;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+
+use crate::{donnees::{igdb::{extra::obtenir_catalogue, interface::CompatibleSQL}, objet::*}, chemin::chemins};
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::arriere_plan::{Evenement, Suivi};
+use crate::outils::dl::err::*;
+use crate::outils::medias::{self, Media, TailleImage};
+
+const TELECHARGEMENTS_SIMULTANES: usize = 4;
+const TENTATIVES: u32 = 3;
+const ATTENTE_INITIALE: u64 = 500;
+
+static TEMPORAIRES: AtomicU64 = AtomicU64::new(0);
+
+pub struct Telechargement {
+    pub url: String,
+    pub destination: PathBuf,
+}
+
+#[derive(Default)]
+pub struct Bilan {
+    pub reussis: u32,
+    pub ignores: u32,
+    pub annules: u32,
+    pub echecs: Vec<(String, Erreur)>,
+}
+
+impl Bilan {
+    pub fn afficher(&self) {
+        for (url, erreur) in &self.echecs {
+            println!("ATTENTION: Téléchargement de {} abandonné.", url);
+            erreur.afficher_attention();
+        }
+
+        println!(
+            "INFO: {} téléchargement(s) réussi(s), {} déjà présent(s), {} échec(s).",
+            self.reussis,
+            self.ignores,
+            self.echecs.len(),
+        );
+
+        if self.annules > 0 {
+            println!("INFO: {} téléchargement(s) annulé(s).", self.annules);
+        }
+    }
+}
+
+// Fichier temporaire dans le même dossier pour que le renommage soit atomique.
+// Le pid et un compteur évitent qu'un autre processus écrive le même fichier.
+fn chemin_temporaire(destination: &Path) -> PathBuf {
+    let nom = destination.file_name()
+        .map(|nom| nom.to_string_lossy().to_string())
+        .unwrap_or(String::from("telechargement"));
+    let numero = TEMPORAIRES.fetch_add(1, Ordering::Relaxed);
+
+    destination.with_file_name(format!(".{}.{}-{}.part", nom, std::process::id(), numero))
+}
+
+async fn telecharger_une_fois(client: &reqwest::Client, url: &str, destination: &Path) -> Result<(), Erreur> {
+    let reponse = match client.get(url).send().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurRequete { erreur, url: url.to_string() }.as_err(),
+    };
+
+    if !reponse.status().is_success() {
+        return ErreurStatut { statut: reponse.status(), url: url.to_string() }.as_err();
+    }
+
+    let type_contenu = reponse.headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|valeur| valeur.to_str().ok())
+        .map(String::from);
+
+    match &type_contenu {
+        Some(valeur) if valeur.starts_with("image/") => {},
+        _ => return ErreurContenu { type_contenu, url: url.to_string() }.as_err(),
+    }
+
+    let contenu = match reponse.bytes().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurRequete { erreur, url: url.to_string() }.as_err(),
+    };
+
+    let temporaire = chemin_temporaire(destination);
+
+    if let Err(erreur) = tokio::fs::write(&temporaire, &contenu).await {
+        let _ = tokio::fs::remove_file(&temporaire).await;
+        return ErreurFichier { erreur, chemin: temporaire }.as_err();
+    }
+
+    if let Err(erreur) = tokio::fs::rename(&temporaire, destination).await {
+        let _ = tokio::fs::remove_file(&temporaire).await;
+        return ErreurFichier { erreur, chemin: destination.to_path_buf() }.as_err();
+    }
+
+    Ok(())
+}
+
+pub async fn telecharger_fichier(client: &reqwest::Client, url: &str, destination: &Path) -> Result<(), Erreur> {
+    let mut tentative = 1;
+
+    loop {
+        match telecharger_une_fois(client, url, destination).await {
+            Ok(_) => return Ok(()),
+            Err(erreur) if erreur.temporaire() && tentative < TENTATIVES => {
+                let attente = ATTENTE_INITIALE * 2u64.pow(tentative - 1);
+                println!("INFO: Nouvelle tentative pour {} dans {} ms.", url, attente);
+                tokio::time::sleep(Duration::from_millis(attente)).await;
+                tentative += 1;
+            },
+            Err(erreur) => return Err(erreur),
+        }
+    }
+}
+
+// Une tâche qui obtient sa place après l'annulation n'est pas lancée.
+pub async fn telecharger(taches: Vec<Telechargement>, suivi: &Suivi) -> Bilan {
+    let mut bilan = Bilan::default();
+
+    let client = medias::CLIENT_MEDIAS.clone();
+    let semaphore = Arc::new(Semaphore::new(TELECHARGEMENTS_SIMULTANES));
+    let mut travaux = vec![];
+    // Plusieurs entrées peuvent partager un fichier (même jeu IGDB): un seul
+    // téléchargement par destination, sinon ils se disputent le fichier temporaire.
+    let mut destinations: HashSet<PathBuf> = HashSet::new();
+
+    for tache in taches {
+        if tache.destination.exists() || !destinations.insert(tache.destination.clone()) {
+            bilan.ignores += 1;
+            continue;
+        }
+
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let suivi = suivi.clone();
+
+        travaux.push(tokio::spawn(async move {
+            let _permis = semaphore.acquire_owned().await;
+
+            if suivi.est_annule() {
+                return (tache.url, None);
+            }
+
+            let resultat = telecharger_fichier(&client, &tache.url, &tache.destination).await;
+            (tache.url, Some(resultat))
+        }));
+    }
+
+    let total = travaux.len();
+    suivi.signaler(Evenement::Progression(0, total));
+
+    for (faits, travail) in travaux.into_iter().enumerate() {
+        match travail.await {
+            Ok((_, Some(Ok(_)))) => bilan.reussis += 1,
+            Ok((url, Some(Err(erreur)))) => bilan.echecs.push((url, erreur)),
+            Ok((_, None)) => bilan.annules += 1,
+            Err(erreur) => println!("ATTENTION: Tâche de téléchargement interrompue: {}", erreur),
+        }
+
+        suivi.signaler(Evenement::Progression(faits + 1, total));
+    }
+
+    bilan
+}
+
+// Les couvertures étaient rangées à la racine du cache, sous l'identifiant du jeu.
+fn reprendre_ancienne_couverture(id: u32, media: &Media) {
+    let ancienne = match chemins::trouver_chemin(format!("{}.jpg", id), chemins::XDG::CACHE) {
+        Ok(valeur) => valeur,
+        Err(_) => return,
+    };
+
+    match media.chemin(TailleImage::CouvertureGrande) {
+        Ok(destination) if !destination.exists() => {
+            if let Err(erreur) = std::fs::rename(&ancienne, &destination) {
+                println!("ATTENTION: Impossible de déplacer {}: {}", ancienne.display(), erreur);
+            }
+        },
+        Ok(_) => {
+            let _ = std::fs::remove_file(&ancienne);
+        },
+        Err(erreur) => erreur.afficher_attention(),
+    }
+}
+
+pub async fn telecharger_couvertures(suivi: &Suivi) -> Bilan {
+    let jeux = obtenir_catalogue().await;
+    let mut couvertures: Vec<Media> = vec![];
+    // Jeux dont la couverture manque encore, pour ne rafraîchir que leurs tuiles.
+    let mut manquantes: Vec<(String, PathBuf)> = vec![];
+
+    for jeu in jeux {
+        if suivi.est_annule() {
+            return Bilan::default();
+        }
+
+        if let Some(id) = jeu.jeu {
+            let jeu_igdb = match JeuIGDB::charger(id).await {
+                Ok(Some(valeur)) => valeur,
+                _ => continue,
+            };
+
+            let media = match medias::couverture(&jeu_igdb) {
+                Some(valeur) => valeur,
+                None => continue,
+            };
+
+            reprendre_ancienne_couverture(id, &media);
+
+            if let Ok(destination) = media.chemin(TailleImage::CouvertureGrande) {
+                if !destination.exists() {
+                    manquantes.push((jeu.chemin, destination));
+                }
+            }
+
+            couvertures.push(media);
+        }
+    }
+
+    let taches: Vec<Telechargement> = couvertures
+        .iter()
+        .filter_map(|media| media.telechargement(TailleImage::CouvertureGrande))
+        .collect();
+
+    let bilan = telecharger(taches, suivi).await;
+    bilan.afficher();
+
+    let nouvelles: HashSet<String> = manquantes
+        .into_iter()
+        .filter(|(_, destination)| destination.exists())
+        .map(|(chemin, _)| chemin)
+        .collect();
+
+    if !nouvelles.is_empty() {
+        suivi.signaler(Evenement::Couvertures(nouvelles));
+    }
+
+    for media in &couvertures {
+        if let Some(chemin) = media.chemin_local(TailleImage::CouvertureGrande) {
+            medias::cache::noter_acces(media, &chemin).await;
+        }
+    }
+
+    bilan
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use crate::interne::erreurs::TraitErreur;
+
+// Erreur Ecriture
+pub struct ErreurEcriture {
+    pub erreur: std::io::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurEcriture {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurEcriture(self))
+    }
+}
+
+impl TraitErreur for ErreurEcriture {
+    fn message(&self) -> String {
+        format!("Impossible d'écrire {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur Serialisation
+pub struct ErreurSerialisation {
+    pub erreur: serde_json::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurSerialisation {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurSerialisation(self))
+    }
+}
+
+impl TraitErreur for ErreurSerialisation {
+    fn message(&self) -> String {
+        format!("Impossible de générer la liste {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurEcriture(ErreurEcriture),
+    ErreurSerialisation(ErreurSerialisation),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurEcriture(erreur) => erreur,
+            Erreur::ErreurSerialisation(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
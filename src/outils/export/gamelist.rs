@@ -0,0 +1,152 @@
+// gamelist.xml d'EmulationStation, un par système. Les chemins sont absolus pour
+// que le fichier reste valable hors du dossier des jeux.
+
+use std::path::{Path, PathBuf};
+
+use crate::interne::date::date_civile;
+use crate::outils::export::err::*;
+use crate::outils::export::{ecrire, Systeme};
+
+// Noms de système d'EmulationStation pour les plateformes IGDB courantes.
+const SYSTEMES_ES: &[(u32, &str)] = &[
+    (18, "nes"),
+    (51, "fds"),
+    (19, "snes"),
+    (4, "n64"),
+    (21, "gc"),
+    (5, "wii"),
+    (33, "gb"),
+    (22, "gbc"),
+    (24, "gba"),
+    (20, "nds"),
+    (37, "3ds"),
+    (87, "virtualboy"),
+    (84, "sg-1000"),
+    (64, "mastersystem"),
+    (29, "megadrive"),
+    (30, "sega32x"),
+    (78, "segacd"),
+    (35, "gamegear"),
+    (32, "saturn"),
+    (23, "dreamcast"),
+    (7, "psx"),
+    (8, "ps2"),
+    (38, "psp"),
+    (59, "atari2600"),
+    (66, "atari5200"),
+    (60, "atari7800"),
+    (62, "atarijaguar"),
+    (61, "atarilynx"),
+    (86, "pcengine"),
+    (119, "ngp"),
+    (120, "ngpc"),
+    (80, "neogeo"),
+    (57, "wonderswan"),
+    (123, "wonderswancolor"),
+    (50, "3do"),
+    (70, "vectrex"),
+    (68, "colecovision"),
+    (67, "intellivision"),
+    (27, "msx"),
+    (15, "c64"),
+    (16, "amiga"),
+    (13, "pc"),
+    (52, "arcade"),
+];
+
+pub fn systeme_es(plateforme: u32) -> Option<&'static str> {
+    SYSTEMES_ES.iter()
+        .find(|(id, _)| *id == plateforme)
+        .map(|(_, nom)| *nom)
+}
+
+fn echapper(texte: &str) -> String {
+    let mut resultat = String::with_capacity(texte.len());
+
+    for caractere in texte.chars() {
+        match caractere {
+            '&' => resultat.push_str("&amp;"),
+            '<' => resultat.push_str("&lt;"),
+            '>' => resultat.push_str("&gt;"),
+            '"' => resultat.push_str("&quot;"),
+            '\'' => resultat.push_str("&apos;"),
+            autre => resultat.push(autre),
+        }
+    }
+
+    resultat
+}
+
+fn balise(xml: &mut String, nom: &str, valeur: &str) {
+    xml.push_str(&format!("\t\t<{}>{}</{}>\n", nom, echapper(valeur), nom));
+}
+
+pub fn generer(systeme: &Systeme) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\"?>\n<gameList>\n");
+
+    for fiche in &systeme.fiches {
+        xml.push_str("\t<game>\n");
+        balise(&mut xml, "path", &fiche.chemin.display().to_string());
+        balise(&mut xml, "name", &fiche.nom);
+
+        if let Some(description) = &fiche.description {
+            balise(&mut xml, "desc", description);
+        }
+        if let Some(couverture) = &fiche.couverture {
+            balise(&mut xml, "image", &couverture.display().to_string());
+        }
+        if let Some(note) = fiche.note {
+            balise(&mut xml, "rating", &format!("{:.2}", (note / 100.0).clamp(0.0, 1.0)));
+        }
+        if let Some(date) = fiche.date {
+            let (annee, mois, jour) = date_civile(date);
+            balise(&mut xml, "releasedate", &format!("{:04}{:02}{:02}T000000", annee, mois, jour));
+        }
+        if !fiche.genres.is_empty() {
+            balise(&mut xml, "genre", &fiche.genres.join(", "));
+        }
+
+        xml.push_str("\t</game>\n");
+    }
+
+    xml.push_str("</gameList>\n");
+    xml
+}
+
+pub fn exporter(systeme: &Systeme, dossier: &Path) -> Result<PathBuf, Erreur> {
+    let chemin = dossier.join(&systeme.code).join("gamelist.xml");
+    ecrire(&chemin, &generer(systeme))?;
+    Ok(chemin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outils::export::FicheExport;
+
+    #[test]
+    fn fiche_complete() {
+        let systeme = Systeme {
+            plateforme: Some(19),
+            code: String::from("snes"),
+            nom: String::from("Super Nintendo"),
+            fiches: vec![FicheExport {
+                chemin: PathBuf::from("/jeux/snes/Chrono Trigger.sfc"),
+                nom: String::from("Chrono Trigger"),
+                description: Some(String::from("Crono & Marle <1995>")),
+                genres: vec![String::from("Role-playing (RPG)"), String::from("Adventure")],
+                date: Some(794880000),
+                note: Some(93.0),
+                couverture: None,
+            }],
+        };
+
+        let xml = generer(&systeme);
+
+        assert!(xml.contains("<desc>Crono &amp; Marle &lt;1995&gt;</desc>"));
+        assert!(xml.contains("<rating>0.93</rating>"));
+        assert!(xml.contains("<releasedate>19950311T000000</releasedate>"));
+        assert!(xml.contains("<genre>Role-playing (RPG), Adventure</genre>"));
+        assert!(roxmltree::Document::parse(&xml).is_ok());
+    }
+}
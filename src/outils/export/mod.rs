@@ -0,0 +1,184 @@
+pub mod err;
+pub mod gamelist;
+pub mod pegasus;
+pub mod retroarch;
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::donnees::igdb::extra::obtenir_catalogue;
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{Jeu, JeuIGDB, PlateformeIGDB};
+use crate::interne::erreurs::{dernier_echec, TraitErreur};
+use crate::outils::export::err::*;
+use crate::outils::fournisseurs::local;
+use crate::outils::images_perso::{image_perso, TypeImage};
+use crate::outils::lanceur::lister_emulateurs;
+use crate::outils::medias::{self, TailleImage};
+
+// Ce que les autres lanceurs savent afficher d'un jeu identifié.
+#[derive(Debug)]
+pub struct FicheExport {
+    pub chemin: PathBuf,
+    pub nom: String,
+    pub description: Option<String>,
+    pub genres: Vec<String>,
+    pub date: Option<i64>,
+    // Sur 100, comme les notes IGDB.
+    pub note: Option<f64>,
+    pub couverture: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct Systeme {
+    pub plateforme: Option<u32>,
+    // Nom court, ex: "snes", utilisé pour les dossiers.
+    pub code: String,
+    pub nom: String,
+    pub fiches: Vec<FicheExport>,
+}
+
+fn non_vide(texte: Option<String>) -> Option<String> {
+    texte.filter(|valeur| !valeur.trim().is_empty())
+}
+
+// Image personnalisée, puis fiche locale, puis couverture IGDB déjà en cache.
+pub async fn couverture(jeu: &Jeu, igdb: &JeuIGDB) -> Option<PathBuf> {
+    if let Some(image) = image_perso(&jeu.chemin, TypeImage::Couverture).await {
+        return Some(image);
+    }
+
+    if local::est_local(igdb.id) {
+        return local::couverture(&jeu.chemin);
+    }
+
+    medias::couverture(igdb).and_then(|media| media.chemin_local(TailleImage::CouvertureGrande))
+}
+
+// Un jeu sort souvent sur plusieurs plateformes: on garde celle de ses émulateurs.
+async fn plateforme_jeu(jeu: &Jeu, igdb: &JeuIGDB) -> Option<u32> {
+    let plateformes = igdb.platforms.clone().unwrap_or(vec![]);
+    let emulateurs = lister_emulateurs(&jeu.chemin).await;
+
+    plateformes.iter()
+        .find(|plateforme| emulateurs.iter().any(|emulateur| emulateur.plateformes.contains(plateforme)))
+        .or(plateformes.first())
+        .copied()
+        .or_else(|| emulateurs.iter().find_map(|emulateur| emulateur.plateformes.first().copied()))
+}
+
+async fn systeme(plateforme: Option<u32>) -> (String, String) {
+    let plateforme = match plateforme {
+        Some(valeur) => valeur,
+        None => return (String::from("autres"), String::from("Autres")),
+    };
+
+    let code = gamelist::systeme_es(plateforme);
+
+    match PlateformeIGDB::charger_traduit(plateforme).await {
+        Ok(Some(valeur)) => (
+            code.map(String::from).or(valeur.slug).unwrap_or(plateforme.to_string()),
+            valeur.name,
+        ),
+        _ => (code.map(String::from).unwrap_or(plateforme.to_string()), plateforme.to_string()),
+    }
+}
+
+pub async fn systemes() -> Vec<Systeme> {
+    let mut systemes: BTreeMap<Option<u32>, Systeme> = BTreeMap::new();
+
+    for jeu in obtenir_catalogue().await {
+        let igdb = match jeu.jeu {
+            Some(id) if id > 0 => match JeuIGDB::charger_traduit(id).await {
+                Ok(Some(valeur)) => valeur,
+                Ok(None) => continue,
+                Err(erreur) => {
+                    erreur.afficher_attention();
+                    continue;
+                },
+            },
+            _ => continue,
+        };
+
+        let plateforme = plateforme_jeu(&jeu, &igdb).await;
+
+        let fiche = FicheExport {
+            chemin: PathBuf::from(&jeu.chemin),
+            nom: igdb.name.clone(),
+            description: non_vide(igdb.summary.clone()),
+            genres: igdb.genres.clone().unwrap_or(vec![]).into_iter().map(|genre| genre.name).collect(),
+            date: igdb.first_release_date,
+            note: igdb.rating,
+            couverture: couverture(&jeu, &igdb).await,
+        };
+
+        let systeme = match systemes.entry(plateforme) {
+            Entry::Occupied(entree) => entree.into_mut(),
+            Entry::Vacant(entree) => {
+                let (code, nom) = systeme(plateforme).await;
+                entree.insert(Systeme { plateforme, code, nom, fiches: vec![] })
+            },
+        };
+
+        systeme.fiches.push(fiche);
+    }
+
+    let mut systemes: Vec<Systeme> = systemes.into_values().collect();
+
+    for systeme in systemes.iter_mut() {
+        systeme.fiches.sort_by_key(|fiche| fiche.nom.to_lowercase());
+    }
+
+    systemes
+}
+
+pub fn ecrire(chemin: &Path, contenu: &str) -> Result<(), Erreur> {
+    if let Some(dossier) = chemin.parent() {
+        if let Err(erreur) = std::fs::create_dir_all(dossier) {
+            return ErreurEcriture { erreur, chemin: chemin.to_path_buf() }.as_err();
+        }
+    }
+
+    match std::fs::write(chemin, contenu) {
+        Ok(_) => Ok(()),
+        Err(erreur) => ErreurEcriture { erreur, chemin: chemin.to_path_buf() }.as_err(),
+    }
+}
+
+// Formats d'export, chacun écrivant un fichier par système.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum FormatExport {
+    // <dossier>/<système>/gamelist.xml (EmulationStation)
+    Gamelist,
+    // <dossier>/<système>/metadata.pegasus.txt
+    Pegasus,
+    // <dossier>/<base libretro>.lpl
+    Retroarch,
+}
+
+pub async fn commande(format: FormatExport, dossier: &Path) -> Result<(), Erreur> {
+    let ecrire_systeme: fn(&Systeme, &Path) -> Result<PathBuf, Erreur> = match format {
+        FormatExport::Gamelist => gamelist::exporter,
+        FormatExport::Pegasus => pegasus::exporter,
+        FormatExport::Retroarch => retroarch::exporter,
+    };
+
+    let systemes = systemes().await;
+    let mut jeux = 0;
+    let mut echecs: Vec<Erreur> = vec![];
+
+    for systeme in &systemes {
+        match ecrire_systeme(systeme, dossier) {
+            Ok(chemin) => {
+                println!("INFO: {} jeu(x) {} exporté(s) dans {}.", systeme.fiches.len(), systeme.nom, chemin.display());
+                jeux += systeme.fiches.len();
+            },
+            Err(erreur) => echecs.push(erreur),
+        }
+    }
+
+    println!("INFO: Export {:?}: {} jeu(x) sur {} système(s).", format, jeux, systemes.len());
+
+    dernier_echec(echecs)
+}
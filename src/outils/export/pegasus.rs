@@ -0,0 +1,68 @@
+// metadata.pegasus.txt du frontal Pegasus, une collection par système:
+//
+// collection: Super Nintendo
+// shortname: snes
+//
+// game: Chrono Trigger
+// file: /jeux/snes/Chrono Trigger.sfc
+// description: Première ligne
+//   ligne suivante, indentée
+//   .
+//   un point seul marque un paragraphe
+
+use std::path::{Path, PathBuf};
+
+use crate::interne::date::date_civile;
+use crate::outils::export::err::*;
+use crate::outils::export::{ecrire, Systeme};
+
+fn champ(texte: &mut String, nom: &str, valeur: &str) {
+    let mut lignes = valeur.lines().map(str::trim_end);
+
+    texte.push_str(&format!("{}: {}\n", nom, lignes.next().unwrap_or("")));
+
+    for ligne in lignes {
+        match ligne.trim().is_empty() {
+            true => texte.push_str("  .\n"),
+            false => texte.push_str(&format!("  {}\n", ligne)),
+        }
+    }
+}
+
+pub fn generer(systeme: &Systeme) -> String {
+    let mut texte = String::new();
+
+    champ(&mut texte, "collection", &systeme.nom);
+    champ(&mut texte, "shortname", &systeme.code);
+
+    for fiche in &systeme.fiches {
+        texte.push('\n');
+        champ(&mut texte, "game", &fiche.nom);
+        champ(&mut texte, "file", &fiche.chemin.display().to_string());
+
+        if let Some(description) = &fiche.description {
+            champ(&mut texte, "description", description);
+        }
+        if !fiche.genres.is_empty() {
+            champ(&mut texte, "genre", &fiche.genres.join(", "));
+        }
+        if let Some(date) = fiche.date {
+            let (annee, mois, jour) = date_civile(date);
+            champ(&mut texte, "release", &format!("{:04}-{:02}-{:02}", annee, mois, jour));
+        }
+        if let Some(note) = fiche.note {
+            champ(&mut texte, "rating", &format!("{}%", note.round().clamp(0.0, 100.0)));
+        }
+        if let Some(couverture) = &fiche.couverture {
+            champ(&mut texte, "assets.boxFront", &couverture.display().to_string());
+        }
+    }
+
+    texte
+}
+
+pub fn exporter(systeme: &Systeme, dossier: &Path) -> Result<PathBuf, Erreur> {
+    let chemin = dossier.join(&systeme.code).join("metadata.pegasus.txt");
+    ecrire(&chemin, &generer(systeme))?;
+    Ok(chemin)
+}
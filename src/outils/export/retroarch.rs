@@ -0,0 +1,64 @@
+// Listes de lecture RetroArch (.lpl, JSON). Le nom de la liste est celui de la base
+// libretro du système, pour que RetroArch retrouve ses vignettes; le cœur est laissé
+// à sa détection.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::outils::export::err::*;
+use crate::outils::export::{ecrire, Systeme};
+use crate::outils::retroarch::base_libretro;
+
+#[derive(Serialize)]
+struct ListeRetroArch {
+    version: &'static str,
+    default_core_path: &'static str,
+    default_core_name: &'static str,
+    items: Vec<ElementListeRetroArch>,
+}
+
+#[derive(Serialize)]
+struct ElementListeRetroArch {
+    path: String,
+    label: String,
+    core_path: &'static str,
+    core_name: &'static str,
+    crc32: &'static str,
+    db_name: String,
+}
+
+fn nom_liste(systeme: &Systeme) -> String {
+    systeme.plateforme
+        .and_then(base_libretro)
+        .map(String::from)
+        .unwrap_or(systeme.nom.replace('/', "-"))
+}
+
+pub fn exporter(systeme: &Systeme, dossier: &Path) -> Result<PathBuf, Erreur> {
+    let nom = nom_liste(systeme);
+    let chemin = dossier.join(format!("{}.lpl", nom));
+
+    let liste = ListeRetroArch {
+        version: "1.5",
+        default_core_path: "",
+        default_core_name: "",
+        items: systeme.fiches.iter()
+            .map(|fiche| ElementListeRetroArch {
+                path: fiche.chemin.display().to_string(),
+                label: fiche.nom.clone(),
+                core_path: "DETECT",
+                core_name: "DETECT",
+                crc32: "DETECT",
+                db_name: format!("{}.lpl", nom),
+            })
+            .collect(),
+    };
+
+    match serde_json::to_string_pretty(&liste) {
+        Ok(contenu) => ecrire(&chemin, &contenu)?,
+        Err(erreur) => return ErreurSerialisation { erreur, chemin }.as_err(),
+    }
+
+    Ok(chemin)
+}
@@ -0,0 +1,46 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::donnees::igdb::obtenir_db;
+use crate::interne::erreurs::TraitErreur;
+
+// Chemins des jeux favoris, du plus récemment ajouté au plus ancien.
+pub async fn favoris() -> Vec<String> {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_attention();
+            return vec![];
+        },
+    };
+
+    match sqlx::query_scalar::<_, String>("SELECT chemin FROM favoris ORDER BY ajoute_le DESC;")
+        .fetch_all(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            vec![]
+        },
+    }
+}
+
+pub async fn est_favori(chemin: &str) -> bool {
+    favoris().await.iter().any(|favori| favori == chemin)
+}
+
+pub async fn definir_favori(chemin: &str, favori: bool) {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return erreur.afficher_attention(),
+    };
+
+    let requete = match favori {
+        true => sqlx::query("INSERT OR IGNORE INTO favoris (chemin, ajoute_le) VALUES (?, ?);")
+            .bind(chemin)
+            .bind(SystemTime::now().duration_since(UNIX_EPOCH).map(|duree| duree.as_secs() as i64).unwrap_or(0)),
+        false => sqlx::query("DELETE FROM favoris WHERE chemin = ?;").bind(chemin),
+    };
+
+    if let Err(erreur) = requete.execute(&db).await {
+        println!("ATTENTION: {}", erreur);
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use crate::{interne::erreurs::TraitErreur, api::igdb::err::Erreur as ErreurIGDB};
+
+// Erreur FournisseurIGDB
+pub struct ErreurFournisseurIGDB {
+    pub erreur: ErreurIGDB,
+}
+
+impl ErreurFournisseurIGDB {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurFournisseurIGDB(self))
+    }
+}
+
+impl TraitErreur for ErreurFournisseurIGDB {
+    fn message(&self) -> String {
+        String::from("Le fournisseur IGDB n'a pas pu répondre.")
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.to_string())
+    }
+}
+
+// Erreur FicheLocale
+pub struct ErreurFicheLocale {
+    pub chemin: PathBuf,
+    pub erreur: String,
+}
+
+impl ErreurFicheLocale {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurFicheLocale(self))
+    }
+}
+
+impl TraitErreur for ErreurFicheLocale {
+    fn message(&self) -> String {
+        format!("Fiche de métadonnées {} illisible.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.clone())
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurFournisseurIGDB(ErreurFournisseurIGDB),
+    ErreurFicheLocale(ErreurFicheLocale),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurFournisseurIGDB(erreur) => erreur,
+            Erreur::ErreurFicheLocale(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
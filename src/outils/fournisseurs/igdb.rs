@@ -0,0 +1,60 @@
+use crate::api::igdb::{requete, ClientIGDB};
+use crate::donnees::objet::JeuIGDB;
+use crate::outils::fournisseurs::err::*;
+use crate::outils::fournisseurs::{local, FournisseurMetadonnees, Recherche};
+use crate::outils::medias::{self, SourceMedia};
+
+pub struct FournisseurIGDB;
+
+async fn client() -> Result<&'static ClientIGDB, Erreur> {
+    match ClientIGDB::partage().await {
+        Ok(valeur) => Ok(valeur),
+        Err(erreur) => ErreurFournisseurIGDB { erreur }.as_err(),
+    }
+}
+
+#[async_trait::async_trait]
+impl FournisseurMetadonnees for FournisseurIGDB {
+    fn nom(&self) -> &'static str {
+        "IGDB"
+    }
+
+    fn gere_id(&self, id: u32) -> bool {
+        !local::est_local(id)
+    }
+
+    // Une multiquery pour 10 recherches.
+    async fn rechercher(&self, recherches: &[Recherche]) -> Result<Vec<Option<JeuIGDB>>, Erreur> {
+        if recherches.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let requetes = recherches.iter()
+            .map(|recherche| requete::requete_trouver_jeu(recherche.nom.clone(), None))
+            .collect();
+
+        match client().await?.solliciter_plusieurs(requetes).await {
+            Ok(reponses) => Ok(reponses.into_iter().map(|reponse| reponse.into_iter().next()).collect()),
+            Err(erreur) => ErreurFournisseurIGDB { erreur }.as_err(),
+        }
+    }
+
+    // Une requête pour 500 identifiants.
+    async fn recuperer(&self, ids: &[u32]) -> Result<Vec<JeuIGDB>, Erreur> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        match client().await?.solliciter_par_lots(ids, requete::requete_recuperer_jeux).await {
+            Ok(valeur) => Ok(valeur),
+            Err(erreur) => ErreurFournisseurIGDB { erreur }.as_err(),
+        }
+    }
+
+    async fn medias(&self, jeu: &JeuIGDB, _chemin: &str) -> Vec<SourceMedia> {
+        medias::medias_jeu(jeu).await
+            .into_iter()
+            .map(SourceMedia::Distant)
+            .collect()
+    }
+}
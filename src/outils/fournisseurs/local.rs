@@ -0,0 +1,209 @@
+// Fiches de métadonnées posées à côté des jeux: <jeu>.json ou <jeu.ext>.json.
+// Les chemins d'images d'une fiche sont relatifs à son dossier.
+
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::Deserialize;
+
+use crate::donnees::igdb::extra::obtenir_catalogue;
+use crate::donnees::objet::{GenreIGDB, JeuIGDB};
+use crate::interne::empreinte::empreinte;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::fournisseurs::err::*;
+use crate::outils::fournisseurs::{FournisseurMetadonnees, Recherche};
+use crate::outils::medias::{SourceMedia, TypeMedia};
+
+// Les identifiants locaux ont le bit de poids fort, hors de la plage d'IGDB.
+const BIT_LOCAL: u32 = 0x8000_0000;
+
+pub struct FournisseurLocal;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct FicheLocale {
+    name: Option<String>,
+    summary: Option<String>,
+    storyline: Option<String>,
+    first_release_date: Option<i64>,
+    genres: Vec<String>,
+    platforms: Vec<u32>,
+    rating: Option<f64>,
+    cover: Option<PathBuf>,
+    artworks: Vec<PathBuf>,
+    screenshots: Vec<PathBuf>,
+}
+
+pub fn est_local(id: u32) -> bool {
+    id & BIT_LOCAL != 0
+}
+
+pub fn id_local(cle: &str) -> u32 {
+    BIT_LOCAL | (empreinte(cle) as u32 & !BIT_LOCAL)
+}
+
+pub fn genre_local(nom: String) -> GenreIGDB {
+    let id = id_local(&format!("genre:{}", nom));
+    GenreIGDB { id, name: nom, slug: Some(format!("local-{}", id)), name_traduit: None, updated_at: None }
+}
+
+fn chemin_fiche(jeu: &Path) -> Option<PathBuf> {
+    let mut candidats = vec![PathBuf::from(format!("{}.json", jeu.display()))];
+
+    if jeu.extension().is_some() {
+        candidats.push(jeu.with_extension("json"));
+    }
+
+    candidats.into_iter().find(|chemin| chemin.is_file())
+}
+
+fn lire_fiche(jeu: &Path) -> Result<Option<(PathBuf, FicheLocale)>, Erreur> {
+    let chemin = match chemin_fiche(jeu) {
+        Some(valeur) => valeur,
+        None => return Ok(None),
+    };
+
+    let contenu = match std::fs::read_to_string(&chemin) {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurFicheLocale { chemin, erreur: erreur.to_string() }.as_err(),
+    };
+
+    match serde_json::from_str::<FicheLocale>(&contenu) {
+        Ok(fiche) => Ok(Some((chemin, fiche))),
+        Err(erreur) => ErreurFicheLocale { chemin, erreur: erreur.to_string() }.as_err(),
+    }
+}
+
+fn date_modification(chemin: &Path) -> Option<i64> {
+    let date = std::fs::metadata(chemin).and_then(|meta| meta.modified()).ok()?;
+    date.duration_since(UNIX_EPOCH).ok().map(|duree| duree.as_secs() as i64)
+}
+
+fn convertir(jeu: &Path, nom: &str, chemin_fiche: &Path, fiche: FicheLocale) -> JeuIGDB {
+    let id = id_local(&jeu.display().to_string());
+
+    let genres = fiche.genres.into_iter().map(genre_local).collect();
+
+    JeuIGDB {
+        id,
+        name: fiche.name.unwrap_or(String::from(nom)),
+        slug: Some(format!("local-{}", id)),
+        name_traduit: None,
+        alternative_names: None,
+        storyline: fiche.storyline,
+        summary: fiche.summary,
+        storyline_traduit: None,
+        summary_traduit: None,
+
+        first_release_date: fiche.first_release_date,
+
+        collection: None,
+        franchise: None,
+        category: None,
+
+        genres: Some(genres),
+        themes: None,
+        keywords: None,
+
+        platforms: Some(fiche.platforms),
+
+        remakes: None,
+        remasters: None,
+        similar_games: None,
+
+        rating: fiche.rating,
+        rating_count: None,
+
+        cover: None,
+        artworks: None,
+        screenshots: None,
+        videos: None,
+
+        updated_at: date_modification(chemin_fiche),
+    }
+}
+
+fn fiche_ou_attention(jeu: &Path) -> Option<(PathBuf, FicheLocale)> {
+    match lire_fiche(jeu) {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_attention();
+            None
+        },
+    }
+}
+
+fn images(jeu: &Path) -> Vec<SourceMedia> {
+    let (chemin, fiche) = match fiche_ou_attention(jeu) {
+        Some(valeur) => valeur,
+        None => return vec![],
+    };
+
+    let dossier = chemin.parent().map(Path::to_path_buf).unwrap_or(PathBuf::new());
+    let mut sources: Vec<SourceMedia> = vec![];
+
+    if let Some(couverture) = fiche.cover {
+        sources.push(SourceMedia::Local(TypeMedia::Couverture, dossier.join(couverture)));
+    }
+    for illustration in fiche.artworks {
+        sources.push(SourceMedia::Local(TypeMedia::Illustration, dossier.join(illustration)));
+    }
+    for capture in fiche.screenshots {
+        sources.push(SourceMedia::Local(TypeMedia::CaptureEcran, dossier.join(capture)));
+    }
+
+    sources
+}
+
+// Couverture citée par la fiche du jeu, pour les vignettes.
+pub fn couverture(jeu: &str) -> Option<PathBuf> {
+    images(Path::new(jeu))
+        .into_iter()
+        .find_map(|source| match source {
+            SourceMedia::Local(TypeMedia::Couverture, chemin) if chemin.is_file() => Some(chemin),
+            _ => None,
+        })
+}
+
+#[async_trait::async_trait]
+impl FournisseurMetadonnees for FournisseurLocal {
+    fn nom(&self) -> &'static str {
+        "local"
+    }
+
+    fn gere_id(&self, id: u32) -> bool {
+        est_local(id)
+    }
+
+    async fn rechercher(&self, recherches: &[Recherche]) -> Result<Vec<Option<JeuIGDB>>, Erreur> {
+        Ok(recherches.iter()
+            .map(|recherche| {
+                fiche_ou_attention(&recherche.chemin)
+                    .map(|(chemin, fiche)| convertir(&recherche.chemin, &recherche.nom, &chemin, fiche))
+            })
+            .collect())
+    }
+
+    // Les fiches ne sont pas indexées: on repasse par les jeux du catalogue.
+    async fn recuperer(&self, ids: &[u32]) -> Result<Vec<JeuIGDB>, Erreur> {
+        let mut jeux: Vec<JeuIGDB> = vec![];
+
+        for jeu in obtenir_catalogue().await {
+            if !jeu.jeu.is_some_and(|id| ids.contains(&id)) {
+                continue;
+            }
+
+            let chemin_jeu = PathBuf::from(&jeu.chemin);
+
+            if let Some((chemin, fiche)) = fiche_ou_attention(&chemin_jeu) {
+                jeux.push(convertir(&chemin_jeu, &jeu.nom, &chemin, fiche));
+            }
+        }
+
+        Ok(jeux)
+    }
+
+    async fn medias(&self, _jeu: &JeuIGDB, chemin: &str) -> Vec<SourceMedia> {
+        images(Path::new(chemin))
+    }
+}
@@ -0,0 +1,63 @@
+pub mod err;
+pub mod igdb;
+pub mod local;
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::donnees::config::{self, Fournisseur};
+use crate::donnees::objet::JeuIGDB;
+use crate::outils::fournisseurs::err::*;
+use crate::outils::medias::SourceMedia;
+
+// Ce que le scanner sait d'un jeu à identifier.
+pub struct Recherche {
+    pub chemin: PathBuf,
+    pub nom: String,
+}
+
+// Source de métadonnées (IGDB, fiches locales…), consultée dans l'ordre de la configuration.
+#[async_trait::async_trait]
+pub trait FournisseurMetadonnees: Send + Sync {
+    fn nom(&self) -> &'static str;
+
+    // Vrai si l'identifiant a été attribué par ce fournisseur.
+    fn gere_id(&self, id: u32) -> bool;
+
+    // Une réponse par recherche, dans le même ordre; None si rien ne correspond.
+    async fn rechercher(&self, recherches: &[Recherche]) -> Result<Vec<Option<JeuIGDB>>, Erreur>;
+
+    async fn recuperer(&self, ids: &[u32]) -> Result<Vec<JeuIGDB>, Erreur>;
+
+    async fn medias(&self, jeu: &JeuIGDB, chemin: &str) -> Vec<SourceMedia>;
+}
+
+fn construire(fournisseur: Fournisseur) -> Box<dyn FournisseurMetadonnees> {
+    match fournisseur {
+        Fournisseur::IGDB => Box::new(igdb::FournisseurIGDB),
+        Fournisseur::Local => Box::new(local::FournisseurLocal),
+    }
+}
+
+// Un fournisseur répété n'est gardé qu'à sa première place.
+pub fn configures() -> Vec<Box<dyn FournisseurMetadonnees>> {
+    let mut vus: HashSet<Fournisseur> = HashSet::new();
+
+    config::obtenir_config().fournisseurs
+        .into_iter()
+        .filter(|fournisseur| vus.insert(*fournisseur))
+        .map(construire)
+        .collect()
+}
+
+// Fournisseur ayant attribué l'identifiant, même s'il n'est plus configuré.
+pub fn fournisseur_id(id: u32) -> Box<dyn FournisseurMetadonnees> {
+    match local::est_local(id) {
+        true => construire(Fournisseur::Local),
+        false => construire(Fournisseur::IGDB),
+    }
+}
+
+pub async fn medias_jeu(jeu: &JeuIGDB, chemin: &str) -> Vec<SourceMedia> {
+    fournisseur_id(jeu.id).medias(jeu, chemin).await
+}
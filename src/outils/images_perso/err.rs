@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use crate::interne::erreurs::TraitErreur;
+
+// Erreur Copie
+pub struct ErreurCopie {
+    pub erreur: std::io::Error,
+    pub source: PathBuf,
+}
+
+impl ErreurCopie {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurCopie(self))
+    }
+}
+
+impl TraitErreur for ErreurCopie {
+    fn message(&self) -> String {
+        format!("Impossible de copier l'image {}.", self.source.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur Format
+pub struct ErreurFormat {
+    pub source: PathBuf,
+}
+
+impl ErreurFormat {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurFormat(self))
+    }
+}
+
+impl TraitErreur for ErreurFormat {
+    fn message(&self) -> String {
+        format!("{} n'est pas une image prise en charge (png, jpg, jpeg, webp).", self.source.display())
+    }
+}
+
+// Erreur Enregistrement
+pub struct ErreurEnregistrement {
+    pub erreur: String,
+    pub chemin: String,
+}
+
+impl ErreurEnregistrement {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurEnregistrement(self))
+    }
+}
+
+impl TraitErreur for ErreurEnregistrement {
+    fn message(&self) -> String {
+        format!("Impossible d'enregistrer l'image personnalisée de {}.", self.chemin)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.clone())
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurCopie(ErreurCopie),
+    ErreurFormat(ErreurFormat),
+    ErreurEnregistrement(ErreurEnregistrement),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurCopie(erreur) => erreur,
+            Erreur::ErreurFormat(erreur) => erreur,
+            Erreur::ErreurEnregistrement(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
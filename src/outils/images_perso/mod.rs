@@ -0,0 +1,191 @@
+pub mod err;
+
+use std::path::{Path, PathBuf};
+
+use crate::chemin::chemins;
+use crate::donnees::igdb::obtenir_db;
+use crate::interne::empreinte::empreinte;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::images_perso::err::*;
+
+const EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+// Dossiers voisins des jeux où chercher les images (ex: covers/<jeu>.png).
+const DOSSIERS_COUVERTURES: &[&str] = &["covers", "boxart", "media/covers"];
+const DOSSIERS_ILLUSTRATIONS: &[&str] = &["fanart", "artworks", "media/fanart"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeImage {
+    Couverture,
+    Illustration,
+}
+
+impl TypeImage {
+    fn nom(&self) -> &'static str {
+        match self {
+            TypeImage::Couverture => "couverture",
+            TypeImage::Illustration => "illustration",
+        }
+    }
+
+    fn fichier_dossier(&self) -> &'static str {
+        match self {
+            TypeImage::Couverture => "cover",
+            TypeImage::Illustration => "fanart",
+        }
+    }
+
+    fn dossiers(&self) -> &'static [&'static str] {
+        match self {
+            TypeImage::Couverture => DOSSIERS_COUVERTURES,
+            TypeImage::Illustration => DOSSIERS_ILLUSTRATIONS,
+        }
+    }
+}
+
+// La base n'a plus d'extension: `with_extension` couperait les titres à leur
+// dernier point (« Dr. Mario (USA) » deviendrait « Dr.png »).
+fn avec_extension(base: &Path) -> Option<PathBuf> {
+    EXTENSIONS.iter()
+        .map(|extension| PathBuf::from(format!("{}.{}", base.display(), extension)))
+        .find(|chemin| chemin.is_file())
+}
+
+// Images déposées à côté du jeu, par ordre de préférence:
+// <jeu>.png, <dossier>/<jeu>.png, puis cover.png pour tout le dossier.
+pub fn image_voisine(chemin: &str, type_image: TypeImage) -> Option<PathBuf> {
+    let jeu = Path::new(chemin);
+    let dossier = jeu.parent()?;
+    let nom = jeu.file_stem()?;
+
+    if type_image == TypeImage::Couverture {
+        if let Some(image) = avec_extension(&dossier.join(nom)) {
+            if image != jeu {
+                return Some(image);
+            }
+        }
+    }
+
+    for sous_dossier in type_image.dossiers() {
+        if let Some(image) = avec_extension(&dossier.join(sous_dossier).join(nom)) {
+            return Some(image);
+        }
+    }
+
+    avec_extension(&dossier.join(type_image.fichier_dossier()))
+}
+
+pub async fn image_choisie(chemin: &str, type_image: TypeImage) -> Option<PathBuf> {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_attention();
+            return None;
+        },
+    };
+
+    match sqlx::query_scalar::<_, String>(
+        "SELECT fichier FROM images_perso WHERE chemin = ? AND type_image = ?;"
+    )
+        .bind(chemin)
+        .bind(type_image.nom())
+        .fetch_optional(&db).await {
+        Ok(valeur) => valeur.map(PathBuf::from).filter(|fichier| fichier.is_file()),
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            None
+        },
+    }
+}
+
+// Image personnalisée d'un jeu: celle choisie dans l'interface, sinon celle déposée à côté.
+pub async fn image_perso(chemin: &str, type_image: TypeImage) -> Option<PathBuf> {
+    match image_choisie(chemin, type_image).await {
+        Some(image) => Some(image),
+        None => image_voisine(chemin, type_image),
+    }
+}
+
+// Copie l'image dans XDG DATA pour qu'elle soit sauvegardée avec la base.
+pub async fn choisir_image(chemin: &str, type_image: TypeImage, source: &Path) -> Result<PathBuf, Erreur> {
+    let extension = match source.extension().map(|extension| extension.to_string_lossy().to_lowercase()) {
+        Some(valeur) if EXTENSIONS.contains(&valeur.as_str()) => valeur,
+        _ => return ErreurFormat { source: source.to_path_buf() }.as_err(),
+    };
+
+    let nom = format!("images/{}/{:016x}.{}", type_image.nom(), empreinte(chemin), extension);
+    let destination = match chemins::determiner_chemin(nom, chemins::XDG::DATA) {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurEnregistrement { erreur: erreur.as_string(), chemin: chemin.to_string() }.as_err(),
+    };
+
+    if let Some(ancienne) = image_choisie(chemin, type_image).await {
+        let _ = std::fs::remove_file(ancienne);
+    }
+
+    if let Err(erreur) = std::fs::copy(source, &destination) {
+        return ErreurCopie { erreur, source: source.to_path_buf() }.as_err();
+    }
+
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurEnregistrement { erreur: erreur.as_string(), chemin: chemin.to_string() }.as_err(),
+    };
+
+    match sqlx::query(
+        "INSERT OR REPLACE INTO images_perso (chemin, type_image, fichier) VALUES (?, ?, ?);"
+    )
+        .bind(chemin)
+        .bind(type_image.nom())
+        .bind(destination.display().to_string())
+        .execute(&db).await {
+        Ok(_) => Ok(destination),
+        Err(erreur) => ErreurEnregistrement { erreur: erreur.to_string(), chemin: chemin.to_string() }.as_err(),
+    }
+}
+
+pub async fn retirer_image(chemin: &str, type_image: TypeImage) -> Result<(), Erreur> {
+    if let Some(ancienne) = image_choisie(chemin, type_image).await {
+        let _ = std::fs::remove_file(ancienne);
+    }
+
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurEnregistrement { erreur: erreur.as_string(), chemin: chemin.to_string() }.as_err(),
+    };
+
+    match sqlx::query("DELETE FROM images_perso WHERE chemin = ? AND type_image = ?;")
+        .bind(chemin)
+        .bind(type_image.nom())
+        .execute(&db).await {
+        Ok(_) => Ok(()),
+        Err(erreur) => ErreurEnregistrement { erreur: erreur.to_string(), chemin: chemin.to_string() }.as_err(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn titres_avec_points() {
+        let dossier = std::env::temp_dir().join(format!("ludotheque-images-{}", std::process::id()));
+        std::fs::create_dir_all(dossier.join("covers")).unwrap();
+        std::fs::write(dossier.join("Super Mario Bros. 3 (USA).png"), b"").unwrap();
+        std::fs::write(dossier.join("covers/Dr. Mario (USA).jpg"), b"").unwrap();
+
+        let jeu = dossier.join("Super Mario Bros. 3 (USA).nes");
+        assert_eq!(
+            image_voisine(&jeu.display().to_string(), TypeImage::Couverture),
+            Some(dossier.join("Super Mario Bros. 3 (USA).png")),
+        );
+
+        let jeu = dossier.join("Dr. Mario (USA).nes");
+        assert_eq!(
+            image_voisine(&jeu.display().to_string(), TypeImage::Couverture),
+            Some(dossier.join("covers/Dr. Mario (USA).jpg")),
+        );
+
+        let _ = std::fs::remove_dir_all(dossier);
+    }
+}
@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use crate::interne::erreurs::TraitErreur;
+
+// Erreur Lecture
+pub struct ErreurLecture {
+    pub erreur: std::io::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurLecture {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurLecture(self))
+    }
+}
+
+impl TraitErreur for ErreurLecture {
+    fn message(&self) -> String {
+        format!("Impossible de lire {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur XML
+pub struct ErreurXML {
+    pub erreur: roxmltree::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurXML {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurXML(self))
+    }
+}
+
+impl TraitErreur for ErreurXML {
+    fn message(&self) -> String {
+        format!("XML invalide dans {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur Format
+pub struct ErreurFormat {
+    pub chemin: PathBuf,
+}
+
+impl ErreurFormat {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurFormat(self))
+    }
+}
+
+impl TraitErreur for ErreurFormat {
+    fn message(&self) -> String {
+        format!("{} n'est ni un gamelist.xml ni une plateforme LaunchBox.", self.chemin.display())
+    }
+}
+
+// Erreur Enregistrement
+pub struct ErreurEnregistrement {
+    pub erreur: String,
+    pub chemin: String,
+}
+
+impl ErreurEnregistrement {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurEnregistrement(self))
+    }
+}
+
+impl TraitErreur for ErreurEnregistrement {
+    fn message(&self) -> String {
+        format!("Impossible d'enregistrer les métadonnées importées de {}.", self.chemin)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.clone())
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurLecture(ErreurLecture),
+    ErreurXML(ErreurXML),
+    ErreurFormat(ErreurFormat),
+    ErreurEnregistrement(ErreurEnregistrement),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurLecture(erreur) => erreur,
+            Erreur::ErreurXML(erreur) => erreur,
+            Erreur::ErreurFormat(erreur) => erreur,
+            Erreur::ErreurEnregistrement(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
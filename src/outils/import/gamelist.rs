@@ -0,0 +1,82 @@
+// gamelist.xml d'EmulationStation: les chemins sont relatifs au dossier du fichier.
+//
+// <gameList>
+//   <game>
+//     <path>./Super Metroid.sfc</path>
+//     <name>Super Metroid</name>
+//     <desc>…</desc>
+//     <rating>0.9</rating>
+//     <releasedate>19940319T000000</releasedate>
+//     <genre>Action, Platform</genre>
+//     <image>./media/covers/Super Metroid.png</image>
+//   </game>
+// </gameList>
+
+use std::path::{Path, PathBuf};
+
+use crate::interne::date::analyser_date;
+use crate::outils::import::{genres, texte, CheminImporte, FicheImportee};
+
+fn resoudre(dossier: &Path, chemin: &str) -> PathBuf {
+    match chemin.strip_prefix("~/") {
+        Some(suite) => dirs::home_dir().unwrap_or_default().join(suite),
+        None => dossier.join(chemin.strip_prefix("./").unwrap_or(chemin)),
+    }
+}
+
+pub fn lire(document: &roxmltree::Document, fichier: &Path) -> Vec<FicheImportee> {
+    let dossier = fichier.parent().unwrap_or(Path::new("."));
+    let mut fiches: Vec<FicheImportee> = vec![];
+
+    for jeu in document.root_element().children().filter(|enfant| enfant.has_tag_name("game")) {
+        let chemin = match texte(jeu, "path") {
+            Some(valeur) => CheminImporte::Resolu(resoudre(dossier, valeur)),
+            None => continue,
+        };
+
+        let image = |balises: &[&str]| balises.iter()
+            .find_map(|balise| texte(jeu, balise))
+            .map(|valeur| resoudre(dossier, valeur));
+
+        fiches.push(FicheImportee {
+            chemin,
+            nom: texte(jeu, "name").map(String::from),
+            description: texte(jeu, "desc").map(String::from),
+            note: texte(jeu, "rating").and_then(|valeur| valeur.parse::<f64>().ok()).map(|note| note * 100.0),
+            date: texte(jeu, "releasedate").and_then(analyser_date),
+            genres: texte(jeu, "genre").map(genres).unwrap_or(vec![]),
+            couverture: image(&["image", "thumbnail"]),
+            illustration: image(&["fanart", "screenshot"]),
+        });
+    }
+
+    fiches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chemins_et_note() {
+        let xml = r#"<gameList>
+            <game>
+                <path>./Super Metroid.sfc</path>
+                <name>Super Metroid</name>
+                <rating>0.9</rating>
+                <image>./media/covers/Super Metroid.png</image>
+            </game>
+            <game>
+                <name>Sans chemin</name>
+            </game>
+        </gameList>"#;
+
+        let document = roxmltree::Document::parse(xml).unwrap();
+        let fiches = lire(&document, Path::new("/jeux/snes/gamelist.xml"));
+
+        assert_eq!(fiches.len(), 1);
+        assert_eq!(fiches[0].chemin, CheminImporte::Resolu(PathBuf::from("/jeux/snes/Super Metroid.sfc")));
+        assert_eq!(fiches[0].couverture, Some(PathBuf::from("/jeux/snes/media/covers/Super Metroid.png")));
+        assert!((fiches[0].note.unwrap() - 90.0).abs() < 1e-9);
+    }
+}
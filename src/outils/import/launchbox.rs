@@ -0,0 +1,132 @@
+// Plateforme LaunchBox: <racine>/Data/Platforms/<plateforme>.xml. Les chemins des jeux
+// sont relatifs à la racine, au format Windows; les images sont rangées dans
+// <racine>/Images/<plateforme>/<type>/[région/]<titre>-01.<ext>.
+
+use std::path::{Path, PathBuf};
+
+use crate::interne::date::analyser_date;
+use crate::outils::import::{genres, texte, CheminImporte, FicheImportee};
+
+const EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+const COUVERTURES: &[&str] = &["Box - Front", "Box - Front - Reconstructed", "Fanart - Box - Front"];
+const ILLUSTRATIONS: &[&str] = &["Fanart - Background", "Screenshot - Gameplay"];
+
+// Un chemin absolu Windows ne peut pas être résolu; seul le nom de fichier servira.
+fn resoudre(racine: &Path, chemin: &str) -> CheminImporte {
+    let chemin = chemin.replace('\\', "/");
+
+    match chemin.find(':') {
+        Some(_) => CheminImporte::Fichier(chemin.rsplit('/').next().unwrap_or(&chemin).into()),
+        None => CheminImporte::Resolu(racine.join(chemin)),
+    }
+}
+
+// LaunchBox remplace les caractères interdits des titres par des soulignés.
+fn nom_image(titre: &str) -> String {
+    titre.chars()
+        .map(|caractere| match caractere {
+            ':' | '\'' | '/' | '\\' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            autre => autre,
+        })
+        .collect()
+}
+
+fn chercher_image(dossier: &Path, nom: &str, profondeur: u32) -> Option<PathBuf> {
+    let entrees: Vec<PathBuf> = std::fs::read_dir(dossier).ok()?.flatten().map(|entree| entree.path()).collect();
+
+    let trouvee = entrees.iter().find(|chemin| {
+        let fichier = chemin.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or(String::new());
+        let extension = chemin.extension().map(|extension| extension.to_string_lossy().to_lowercase()).unwrap_or(String::new());
+
+        fichier.strip_prefix(nom).is_some_and(|suite| suite.starts_with("-0"))
+            && EXTENSIONS.contains(&extension.as_str())
+    });
+
+    if let Some(chemin) = trouvee {
+        return Some(chemin.clone());
+    }
+
+    match profondeur {
+        0 => None,
+        _ => entrees.iter()
+            .filter(|chemin| chemin.is_dir())
+            .find_map(|sous_dossier| chercher_image(sous_dossier, nom, profondeur - 1)),
+    }
+}
+
+fn image(racine: &Path, plateforme: &str, titre: &str, types: &[&str]) -> Option<PathBuf> {
+    let nom = nom_image(titre);
+
+    types.iter().find_map(|type_image| {
+        chercher_image(&racine.join("Images").join(plateforme).join(type_image), &nom, 1)
+    })
+}
+
+fn note(jeu: roxmltree::Node) -> Option<f64> {
+    ["StarRatingFloat", "StarRating", "CommunityStarRating"].iter()
+        .filter_map(|balise| texte(jeu, balise))
+        .filter_map(|valeur| valeur.parse::<f64>().ok())
+        .find(|note| *note > 0.0)
+        .map(|note| note * 20.0)
+}
+
+pub fn lire(document: &roxmltree::Document, fichier: &Path) -> Vec<FicheImportee> {
+    let racine = fichier.ancestors().nth(3).unwrap_or(Path::new("."));
+    let mut fiches: Vec<FicheImportee> = vec![];
+
+    for jeu in document.root_element().children().filter(|enfant| enfant.has_tag_name("Game")) {
+        let chemin = match texte(jeu, "ApplicationPath") {
+            Some(valeur) => resoudre(racine, valeur),
+            None => continue,
+        };
+
+        let titre = texte(jeu, "Title");
+        let plateforme = texte(jeu, "Platform").unwrap_or("");
+
+        fiches.push(FicheImportee {
+            chemin,
+            nom: titre.map(String::from),
+            description: texte(jeu, "Notes").map(String::from),
+            note: note(jeu),
+            date: texte(jeu, "ReleaseDate").and_then(analyser_date),
+            genres: texte(jeu, "Genre").map(genres).unwrap_or(vec![]),
+            couverture: titre.and_then(|titre| image(racine, plateforme, titre, COUVERTURES)),
+            illustration: titre.and_then(|titre| image(racine, plateforme, titre, ILLUSTRATIONS)),
+        });
+    }
+
+    fiches
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+    use super::*;
+
+    #[test]
+    fn chemins_et_note() {
+        let xml = r#"<LaunchBox>
+            <Game>
+                <Title>Super Metroid</Title>
+                <ApplicationPath>Games\SNES\Super Metroid.sfc</ApplicationPath>
+                <Platform>Super Nintendo</Platform>
+                <StarRatingFloat>4.5</StarRatingFloat>
+            </Game>
+            <Game>
+                <Title>Zelda</Title>
+                <ApplicationPath>C:\Jeux\Zelda.sfc</ApplicationPath>
+                <StarRatingFloat>0</StarRatingFloat>
+                <CommunityStarRating>4</CommunityStarRating>
+            </Game>
+        </LaunchBox>"#;
+
+        let document = roxmltree::Document::parse(xml).unwrap();
+        let fiches = lire(&document, Path::new("/LaunchBox/Data/Platforms/Super Nintendo.xml"));
+
+        assert_eq!(fiches.len(), 2);
+        assert_eq!(fiches[0].chemin, CheminImporte::Resolu(PathBuf::from("/LaunchBox/Games/SNES/Super Metroid.sfc")));
+        assert_eq!(fiches[0].note, Some(90.0));
+        assert_eq!(fiches[1].chemin, CheminImporte::Fichier(OsString::from("Zelda.sfc")));
+        assert_eq!(fiches[1].note, Some(80.0));
+    }
+}
@@ -0,0 +1,456 @@
+pub mod err;
+pub mod gamelist;
+pub mod launchbox;
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use sqlx::{Pool, Sqlite};
+
+use crate::donnees::igdb::extra::obtenir_catalogue;
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::igdb::obtenir_db;
+use crate::donnees::objet::{Jeu, JeuIGDB};
+use crate::interne::erreurs::{dernier_echec, TraitErreur};
+use crate::outils::fournisseurs::local;
+use crate::outils::images_perso::{self, TypeImage};
+use crate::outils::import::err::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceImport {
+    Gamelist,
+    LaunchBox,
+}
+
+impl SourceImport {
+    pub fn nom(&self) -> &'static str {
+        match self {
+            SourceImport::Gamelist => "gamelist",
+            SourceImport::LaunchBox => "launchbox",
+        }
+    }
+}
+
+// Chemin d'un jeu dans les fichiers d'un autre lanceur. Un chemin d'un autre système
+// (ex: absolu Windows) n'est pas résolu: seul son nom de fichier est gardé, pour
+// retrouver un jeu déjà au catalogue.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheminImporte {
+    Resolu(PathBuf),
+    Fichier(OsString),
+}
+
+impl Default for CheminImporte {
+    fn default() -> Self {
+        CheminImporte::Resolu(PathBuf::new())
+    }
+}
+
+// Métadonnées d'un jeu lues dans les fichiers d'un autre lanceur.
+#[derive(Debug, Default)]
+pub struct FicheImportee {
+    pub chemin: CheminImporte,
+    pub nom: Option<String>,
+    pub description: Option<String>,
+    // Sur 100, comme les notes IGDB.
+    pub note: Option<f64>,
+    pub date: Option<i64>,
+    pub genres: Vec<String>,
+    pub couverture: Option<PathBuf>,
+    pub illustration: Option<PathBuf>,
+}
+
+#[derive(Default)]
+pub struct Bilan {
+    pub attaches: u32,
+    pub crees: u32,
+    pub ignores: u32,
+}
+
+// Les jeux sont retrouvés par chemin, sinon par nom de fichier s'il est unique
+// (ex: chemins Windows de LaunchBox).
+struct Index {
+    par_chemin: HashMap<PathBuf, Jeu>,
+    par_fichier: HashMap<OsString, Vec<Jeu>>,
+}
+
+fn canonique(chemin: &Path) -> PathBuf {
+    std::fs::canonicalize(chemin).unwrap_or(chemin.to_path_buf())
+}
+
+impl Index {
+    async fn new() -> Index {
+        let mut index = Index { par_chemin: HashMap::new(), par_fichier: HashMap::new() };
+
+        for jeu in obtenir_catalogue().await {
+            let chemin = PathBuf::from(&jeu.chemin);
+
+            if let Some(fichier) = chemin.file_name() {
+                index.par_fichier.entry(fichier.to_os_string()).or_default().push(jeu.clone());
+            }
+            index.par_chemin.insert(canonique(&chemin), jeu);
+        }
+
+        index
+    }
+
+    fn trouver(&self, chemin: &CheminImporte) -> Option<Jeu> {
+        let fichier = match chemin {
+            CheminImporte::Resolu(chemin) => {
+                if let Some(jeu) = self.par_chemin.get(&canonique(chemin)) {
+                    return Some(jeu.clone());
+                }
+                chemin.file_name()?
+            },
+            CheminImporte::Fichier(fichier) => fichier.as_os_str(),
+        };
+
+        match self.par_fichier.get(fichier) {
+            Some(jeux) if jeux.len() == 1 => Some(jeux[0].clone()),
+            _ => None,
+        }
+    }
+}
+
+// Rend le nombre de lignes modifiées.
+async fn executer<'a>(db: &Pool<Sqlite>, requete: sqlx::query::Query<'a, Sqlite, sqlx::sqlite::SqliteArguments<'a>>, chemin: &str) -> Result<u64, Erreur> {
+    match requete.execute(db).await {
+        Ok(valeur) => Ok(valeur.rows_affected()),
+        Err(erreur) => ErreurEnregistrement { erreur: erreur.to_string(), chemin: chemin.to_string() }.as_err(),
+    }
+}
+
+// Champs de `jeux` venant d'un import, que les rafraîchissements IGDB doivent garder.
+pub async fn champs_importes(jeu: u32) -> Vec<String> {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_attention();
+            return vec![];
+        },
+    };
+
+    match sqlx::query_scalar::<_, String>("SELECT champ FROM sources_metadonnees WHERE jeu = ?;")
+        .bind(jeu)
+        .fetch_all(&db).await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            vec![]
+        },
+    }
+}
+
+pub fn conserver_importes(nouveau: &mut JeuIGDB, ancien: &JeuIGDB, champs: &[String]) {
+    for champ in champs {
+        match champ.as_str() {
+            "name" => {
+                nouveau.name = ancien.name.clone();
+                nouveau.name_traduit = ancien.name_traduit.clone();
+            },
+            "summary" => {
+                nouveau.summary = ancien.summary.clone();
+                nouveau.summary_traduit = ancien.summary_traduit.clone();
+            },
+            "rating" => nouveau.rating = ancien.rating,
+            "first_release_date" => nouveau.first_release_date = ancien.first_release_date,
+            "genres" => nouveau.genres = ancien.genres.clone(),
+            _ => {},
+        }
+    }
+}
+
+async fn marquer(db: &Pool<Sqlite>, jeu: u32, champ: &str, source: SourceImport, chemin: &str) -> Result<(), Erreur> {
+    executer(
+        db,
+        sqlx::query("INSERT OR REPLACE INTO sources_metadonnees (jeu, champ, source) VALUES (?, ?, ?);")
+            .bind(jeu)
+            .bind(champ)
+            .bind(source.nom()),
+        chemin,
+    ).await?;
+
+    Ok(())
+}
+
+// Fiche minimale pour les jeux qu'IGDB n'a pas identifiés.
+fn jeu_local(chemin: &str, nom: &str) -> JeuIGDB {
+    let id = local::id_local(chemin);
+
+    JeuIGDB {
+        id,
+        name: String::from(nom),
+        slug: Some(format!("local-{}", id)),
+        name_traduit: None,
+        alternative_names: None,
+        storyline: None,
+        summary: None,
+        storyline_traduit: None,
+        summary_traduit: None,
+        first_release_date: None,
+        collection: None,
+        franchise: None,
+        category: None,
+        genres: None,
+        themes: None,
+        keywords: None,
+        platforms: None,
+        remakes: None,
+        remasters: None,
+        similar_games: None,
+        rating: None,
+        rating_count: None,
+        cover: None,
+        artworks: None,
+        screenshots: None,
+        videos: None,
+        updated_at: None,
+    }
+}
+
+async fn assurer_jeu(db: &Pool<Sqlite>, jeu: &Jeu, fiche: &FicheImportee) -> Result<u32, Erreur> {
+    if let Some(id) = jeu.jeu {
+        if id > 0 && JeuIGDB::existe(id).await.unwrap_or(false) {
+            return Ok(id);
+        }
+    }
+
+    let jeu_igdb = jeu_local(&jeu.chemin, fiche.nom.as_deref().unwrap_or(&jeu.nom));
+
+    if let Err(erreur) = jeu_igdb.enregistrer().await {
+        return ErreurEnregistrement { erreur: erreur.to_string(), chemin: jeu.chemin.clone() }.as_err();
+    }
+
+    executer(
+        db,
+        sqlx::query("UPDATE catalogue SET jeu = ? WHERE chemin = ?;").bind(jeu_igdb.id).bind(&jeu.chemin),
+        &jeu.chemin,
+    ).await?;
+
+    Ok(jeu_igdb.id)
+}
+
+// Une fiche IGDB est partagée par tous les fichiers du même jeu: l'import n'y remplit
+// que les champs vides. Une fiche locale n'appartient qu'à ce fichier et est écrasée.
+async fn appliquer(db: &Pool<Sqlite>, id: u32, chemin: &str, fiche: FicheImportee, source: SourceImport) -> Result<(), Erreur> {
+    let proprietaire = local::est_local(id);
+    let condition = |champ: &str| match proprietaire {
+        true => String::new(),
+        false => format!(" AND {} IS NULL", champ),
+    };
+
+    if let Some(nom) = &fiche.nom {
+        executer(db, sqlx::query("UPDATE catalogue SET nom = ? WHERE chemin = ?;").bind(nom).bind(chemin), chemin).await?;
+
+        if proprietaire {
+            executer(db, sqlx::query("UPDATE jeux SET name = ?, name_traduit = NULL WHERE id = ?;").bind(nom).bind(id), chemin).await?;
+            marquer(db, id, "name", source, chemin).await?;
+        }
+    }
+
+    if let Some(description) = &fiche.description {
+        let sql = format!("UPDATE jeux SET summary = ?, summary_traduit = NULL WHERE id = ?{};", condition("summary"));
+        if executer(db, sqlx::query(&sql).bind(description).bind(id), chemin).await? > 0 {
+            marquer(db, id, "summary", source, chemin).await?;
+        }
+    }
+
+    if let Some(note) = fiche.note {
+        let sql = format!("UPDATE jeux SET rating = ? WHERE id = ?{};", condition("rating"));
+        if executer(db, sqlx::query(&sql).bind(note).bind(id), chemin).await? > 0 {
+            marquer(db, id, "rating", source, chemin).await?;
+        }
+    }
+
+    if let Some(date) = fiche.date {
+        let sql = format!("UPDATE jeux SET first_release_date = ? WHERE id = ?{};", condition("first_release_date"));
+        if executer(db, sqlx::query(&sql).bind(date).bind(id), chemin).await? > 0 {
+            marquer(db, id, "first_release_date", source, chemin).await?;
+        }
+    }
+
+    let genres_connus = match proprietaire {
+        true => 0,
+        false => sqlx::query_scalar::<_, i64>("SELECT count(*) FROM jeux_genres WHERE jeu = ?;")
+            .bind(id)
+            .fetch_one(db).await
+            .unwrap_or(1),
+    };
+
+    if !fiche.genres.is_empty() && genres_connus == 0 {
+        executer(db, sqlx::query("DELETE FROM jeux_genres WHERE jeu = ?;").bind(id), chemin).await?;
+
+        for genre in fiche.genres.into_iter().map(local::genre_local) {
+            if let Err(erreur) = genre.enregistrer().await {
+                return ErreurEnregistrement { erreur: erreur.to_string(), chemin: chemin.to_string() }.as_err();
+            }
+            executer(db, sqlx::query("INSERT INTO jeux_genres (jeu, genre) VALUES (?, ?);").bind(id).bind(genre.id), chemin).await?;
+        }
+        marquer(db, id, "genres", source, chemin).await?;
+    }
+
+    // Une image choisie dans l'interface reste prioritaire.
+    for (type_image, image) in [(TypeImage::Couverture, fiche.couverture), (TypeImage::Illustration, fiche.illustration)] {
+        let image = match image {
+            Some(valeur) if valeur.is_file() => valeur,
+            _ => continue,
+        };
+
+        if images_perso::image_choisie(chemin, type_image).await.is_none() {
+            if let Err(erreur) = images_perso::choisir_image(chemin, type_image, &image).await {
+                erreur.afficher_attention();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn importer(fiches: Vec<FicheImportee>, source: SourceImport) -> Bilan {
+    let mut bilan = Bilan::default();
+
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_erreur();
+            return bilan;
+        },
+    };
+
+    let index = Index::new().await;
+
+    for fiche in fiches {
+        let jeu = match (index.trouver(&fiche.chemin), &fiche.chemin) {
+            (Some(valeur), _) => valeur,
+            (None, CheminImporte::Resolu(chemin)) if chemin.is_file() => {
+                let jeu = Jeu {
+                    jeu: None,
+                    chemin: chemin.display().to_string(),
+                    nom: fiche.nom.clone().unwrap_or(String::new()),
+                    langue: String::new(),
+                };
+
+                if let Err(erreur) = jeu.enregistrer().await {
+                    erreur.afficher_attention();
+                    bilan.ignores += 1;
+                    continue;
+                }
+
+                bilan.crees += 1;
+                jeu
+            },
+            (None, _) => {
+                bilan.ignores += 1;
+                continue;
+            },
+        };
+
+        let resultat = match assurer_jeu(&db, &jeu, &fiche).await {
+            Ok(id) => appliquer(&db, id, &jeu.chemin, fiche, source).await,
+            Err(erreur) => Err(erreur),
+        };
+
+        match resultat {
+            Ok(_) => bilan.attaches += 1,
+            Err(erreur) => {
+                erreur.afficher_attention();
+                bilan.ignores += 1;
+            },
+        }
+    }
+
+    println!(
+        "INFO: Import {}: {} jeu(x) mis à jour dont {} ajouté(s) au catalogue, {} ignoré(s).",
+        source.nom(),
+        bilan.attaches,
+        bilan.crees,
+        bilan.ignores,
+    );
+
+    bilan
+}
+
+// Texte d'une balise enfant, sans les blancs autour; absent s'il est vide.
+fn texte<'a>(jeu: roxmltree::Node<'a, '_>, balise: &str) -> Option<&'a str> {
+    jeu.children()
+        .find(|enfant| enfant.has_tag_name(balise))
+        .and_then(|enfant| enfant.text())
+        .map(str::trim)
+        .filter(|valeur| !valeur.is_empty())
+}
+
+fn genres(texte: &str) -> Vec<String> {
+    texte.split([',', ';', '/'])
+        .map(str::trim)
+        .filter(|genre| !genre.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// Fichier XML d'un autre lanceur, reconnu à sa racine.
+pub async fn importer_fichier(fichier: &Path) -> Result<Bilan, Erreur> {
+    let contenu = match std::fs::read_to_string(fichier) {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurLecture { erreur, chemin: fichier.to_path_buf() }.as_err(),
+    };
+
+    let document = match roxmltree::Document::parse(&contenu) {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurXML { erreur, chemin: fichier.to_path_buf() }.as_err(),
+    };
+
+    let (fiches, source) = match document.root_element().tag_name().name() {
+        "gameList" => (gamelist::lire(&document, fichier), SourceImport::Gamelist),
+        "LaunchBox" => (launchbox::lire(&document, fichier), SourceImport::LaunchBox),
+        _ => return ErreurFormat { chemin: fichier.to_path_buf() }.as_err(),
+    };
+
+    Ok(importer(fiches, source).await)
+}
+
+// Un dossier LaunchBox (Data/Platforms/*.xml) ou un dossier de gamelist.xml
+// (lui-même et ses sous-dossiers directs, comme ~/.emulationstation/gamelists).
+fn fichiers_dossier(dossier: &Path) -> Vec<PathBuf> {
+    let plateformes = dossier.join("Data").join("Platforms");
+
+    if plateformes.is_dir() {
+        return std::fs::read_dir(plateformes)
+            .map(|entrees| entrees.flatten().map(|entree| entree.path()).collect())
+            .unwrap_or(vec![])
+            .into_iter()
+            .filter(|chemin: &PathBuf| chemin.extension().is_some_and(|extension| extension == "xml"))
+            .collect();
+    }
+
+    let mut fichiers = vec![dossier.join("gamelist.xml")];
+
+    if let Ok(entrees) = std::fs::read_dir(dossier) {
+        fichiers.extend(entrees.flatten().map(|entree| entree.path().join("gamelist.xml")));
+    }
+
+    fichiers.into_iter().filter(|chemin| chemin.is_file()).collect()
+}
+
+pub async fn commande(chemins: &[PathBuf]) -> Result<(), Erreur> {
+    let mut echecs: Vec<Erreur> = vec![];
+
+    for chemin in chemins {
+        let fichiers = match chemin.is_dir() {
+            true => fichiers_dossier(chemin),
+            false => vec![chemin.clone()],
+        };
+
+        if fichiers.is_empty() {
+            println!("ATTENTION: Aucun fichier à importer dans {}.", chemin.display());
+        }
+
+        for fichier in fichiers {
+            if let Err(erreur) = importer_fichier(&fichier).await {
+                echecs.push(erreur);
+            }
+        }
+    }
+
+    dernier_echec(echecs)
+}
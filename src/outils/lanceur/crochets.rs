@@ -0,0 +1,72 @@
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::donnees::config::Crochet;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::lanceur::err::*;
+
+const DELAI_DEFAUT: u64 = 30;
+
+pub fn executer_crochet(crochet: &Crochet, variables: &[(String, String)]) -> Result<(), Erreur> {
+    let delai = Duration::from_secs(crochet.delai.unwrap_or(DELAI_DEFAUT));
+
+    let mut processus = match Command::new("sh")
+        .arg("-c")
+        .arg(&crochet.commande)
+        .envs(variables.iter().map(|(cle, valeur)| (cle, valeur)))
+        // Groupe de processus propre au crochet, pour arrêter aussi ce que `sh` a lancé.
+        .process_group(0)
+        .spawn() {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurCrochet {
+            erreur: Some(erreur.to_string()),
+            desc: "lancement impossible.",
+            commande: crochet.commande.clone(),
+        }.as_err(),
+    };
+
+    let debut = Instant::now();
+
+    loop {
+        match processus.try_wait() {
+            Ok(Some(statut)) if statut.success() => return Ok(()),
+            Ok(Some(statut)) => return ErreurCrochet {
+                erreur: Some(statut.to_string()),
+                desc: "le crochet s'est terminé en erreur.",
+                commande: crochet.commande.clone(),
+            }.as_err(),
+            Ok(None) if debut.elapsed() > delai => {
+                unsafe { libc::kill(-(processus.id() as libc::pid_t), libc::SIGKILL); }
+                let _ = processus.wait();
+                return ErreurCrochet {
+                    erreur: None,
+                    desc: "délai dépassé.",
+                    commande: crochet.commande.clone(),
+                }.as_err();
+            },
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(erreur) => return ErreurCrochet {
+                erreur: Some(erreur.to_string()),
+                desc: "impossible d'attendre la fin du crochet.",
+                commande: crochet.commande.clone(),
+            }.as_err(),
+        }
+    }
+}
+
+pub fn executer_crochets_avant(crochets: &[Crochet], variables: &[(String, String)]) -> Result<(), Erreur> {
+    for crochet in crochets {
+        executer_crochet(crochet, variables)?;
+    }
+
+    Ok(())
+}
+
+pub fn executer_crochets_apres(crochets: &[Crochet], variables: &[(String, String)]) {
+    for crochet in crochets {
+        if let Err(erreur) = executer_crochet(crochet, variables) {
+            erreur.afficher_attention();
+        }
+    }
+}
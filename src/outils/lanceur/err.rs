@@ -0,0 +1,126 @@
+use crate::{interne::erreurs::TraitErreur, donnees::igdb::err::Erreur as ErreurDB};
+
+// Erreur AucunEmulateur
+pub struct ErreurAucunEmulateur {
+    pub chemin: String,
+}
+
+impl ErreurAucunEmulateur {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurAucunEmulateur(self))
+    }
+}
+
+impl TraitErreur for ErreurAucunEmulateur {
+    fn message(&self) -> String {
+        format!("Aucun émulateur trouvé pour {}.", self.chemin)
+    }
+}
+
+// Erreur Surcharge
+pub struct ErreurSurcharge {
+    pub erreur: ErreurDB,
+    pub chemin: String,
+}
+
+impl ErreurSurcharge {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurSurcharge(self))
+    }
+}
+
+impl TraitErreur for ErreurSurcharge {
+    fn message(&self) -> String {
+        format!("Impossible de charger les options de lancement de {}.", self.chemin)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.as_string())
+    }
+}
+
+// Erreur Lancement
+pub struct ErreurLancement {
+    pub erreur: std::io::Error,
+    pub commande: String,
+}
+
+impl ErreurLancement {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurLancement(self))
+    }
+}
+
+impl TraitErreur for ErreurLancement {
+    fn message(&self) -> String {
+        format!("Le lancement du jeu a échoué.\nCommande: {}", self.commande)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur Crochet
+pub struct ErreurCrochet {
+    pub erreur: Option<String>,
+    pub desc: &'static str,
+    pub commande: String,
+}
+
+impl ErreurCrochet {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurCrochet(self))
+    }
+}
+
+impl TraitErreur for ErreurCrochet {
+    fn message(&self) -> String {
+        format!("Le crochet \"{}\" a échoué: {}", self.commande, self.desc)
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.erreur.clone()
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurAucunEmulateur(ErreurAucunEmulateur),
+    ErreurSurcharge(ErreurSurcharge),
+    ErreurLancement(ErreurLancement),
+    ErreurCrochet(ErreurCrochet),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurAucunEmulateur(erreur) => erreur,
+            Erreur::ErreurSurcharge(erreur) => erreur,
+            Erreur::ErreurLancement(erreur) => erreur,
+            Erreur::ErreurCrochet(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
@@ -0,0 +1,423 @@
+pub mod crochets;
+pub mod err;
+
+use std::path::Path;
+use std::process::{Child, Command};
+
+use crate::donnees::config::{obtenir_config, Crochet, Crochets, Emulateur, LudothequeConfig, TypeEmulateur};
+use crate::donnees::igdb::extra::obtenir_plateformes;
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{Jeu, SurchargeLancement};
+use crate::outils::lanceur::crochets::{executer_crochets_apres, executer_crochets_avant};
+use crate::outils::lanceur::err::*;
+use crate::outils::{natif, parties, retroarch};
+
+pub enum Regle {
+    Catalogue(String),
+    Plateforme(u32, String),
+    Extension(String, String),
+    Natif(String),
+}
+
+impl std::fmt::Display for Regle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Regle::Catalogue(emulateur) =>
+                write!(f, "{} choisi par la surcharge du catalogue.", emulateur),
+            Regle::Plateforme(plateforme, emulateur) =>
+                write!(f, "{} choisi par défaut pour la plateforme {}.", emulateur, plateforme),
+            Regle::Extension(extension, emulateur) =>
+                write!(f, "{} choisi par défaut pour l'extension .{}.", emulateur, extension),
+            Regle::Natif(emulateur) =>
+                write!(f, "{} choisi par défaut pour un exécutable natif.", emulateur),
+        }
+    }
+}
+
+pub struct Resolution {
+    pub emulateur: Emulateur,
+    pub regle: Regle,
+}
+
+fn emulateur_integre(nom: &str, commande: String, extensions: &[&str], type_emulateur: TypeEmulateur) -> Emulateur {
+    Emulateur {
+        nom: String::from(nom),
+        commande,
+        extensions: extensions.iter().map(|extension| String::from(*extension)).collect(),
+        priorite: -1,
+        plateformes: vec![],
+        type_emulateur,
+        coeur: None,
+        crochets: Crochets::default(),
+    }
+}
+
+// Installateurs et utilitaires livrés avec les jeux Windows, reconnus au début de leur nom.
+const UTILITAIRES_WINDOWS: &[&str] = &[
+    "setup", "install", "unins", "vcredist", "vc_redist", "dxsetup", "dxwebsetup",
+    "dotnetfx", "oalinst", "physx", "ue4prereqsetup", "crashreport",
+];
+
+pub fn est_utilitaire_windows(chemin: &Path) -> bool {
+    let extension = chemin.extension().map(|extension| extension.to_string_lossy().to_lowercase());
+
+    if !matches!(extension.as_deref(), Some("exe") | Some("bat")) {
+        return false;
+    }
+
+    match chemin.file_stem() {
+        Some(nom) => {
+            let nom = nom.to_string_lossy().to_lowercase();
+            UTILITAIRES_WINDOWS.iter().any(|prefixe| nom.starts_with(prefixe))
+        },
+        None => false,
+    }
+}
+
+pub fn emulateurs_integres(config: &LudothequeConfig) -> Vec<Emulateur> {
+    let mut emulateurs: Vec<Emulateur> = vec![];
+
+    if config.jeux_natifs {
+        emulateurs.push(emulateur_integre("Natif", String::new(), &[], TypeEmulateur::Natif));
+        emulateurs.push(emulateur_integre("Raccourci .desktop", String::new(), &["desktop"], TypeEmulateur::Desktop));
+        emulateurs.push(emulateur_integre("AppImage", String::new(), &["AppImage", "appimage"], TypeEmulateur::AppImage));
+    }
+
+    if let Some(wine) = &config.wine {
+        emulateurs.push(emulateur_integre("Wine", wine.commande.clone(), &["exe", "bat"], TypeEmulateur::Wine));
+    }
+
+    if let Some(proton) = &config.proton {
+        emulateurs.push(emulateur_integre(
+            "Proton",
+            proton.commande.display().to_string(),
+            &["exe", "bat"],
+            TypeEmulateur::Proton,
+        ));
+    }
+
+    emulateurs
+}
+
+pub fn tous_emulateurs(config: &LudothequeConfig) -> Vec<Emulateur> {
+    let mut emulateurs = config.emulateurs.clone();
+    emulateurs.extend(emulateurs_integres(config));
+
+    emulateurs
+}
+
+fn extension_compatible(config: &LudothequeConfig, emulateur: &Emulateur, chemin: &str) -> Option<String> {
+    if emulateur.type_emulateur == TypeEmulateur::Natif {
+        return match natif::est_jeu_natif(config, Path::new(chemin)) {
+            true => Some(String::new()),
+            false => None,
+        };
+    }
+
+    let windows = matches!(emulateur.type_emulateur, TypeEmulateur::Wine | TypeEmulateur::Proton);
+    if windows && est_utilitaire_windows(Path::new(chemin)) {
+        return None;
+    }
+
+    for extension in &emulateur.extensions {
+        if chemin.ends_with(format!(".{}", extension).as_str()) {
+            return Some(extension.clone());
+        }
+    }
+
+    None
+}
+
+fn trouver_emulateur(config: &LudothequeConfig, nom: &str) -> Option<Emulateur> {
+    tous_emulateurs(config).into_iter().find(|emulateur| emulateur.nom == nom)
+}
+
+fn plateforme_compatible(emulateur: &Emulateur, plateformes: &[u32]) -> bool {
+    emulateur.plateformes.is_empty()
+        || plateformes.is_empty()
+        || emulateur.plateformes.iter().any(|plateforme| plateformes.contains(plateforme))
+}
+
+fn emulateurs_compatibles(config: &LudothequeConfig, chemin: &str, plateformes: &[u32]) -> Vec<Emulateur> {
+    let mut emulateurs: Vec<Emulateur> = tous_emulateurs(config)
+        .into_iter()
+        .filter(|emulateur| extension_compatible(config, emulateur, chemin).is_some())
+        .filter(|emulateur| plateforme_compatible(emulateur, plateformes))
+        .collect();
+
+    emulateurs.sort_by_key(|emulateur| std::cmp::Reverse(emulateur.priorite));
+
+    emulateurs
+}
+
+async fn plateformes_jeu(chemin: &str) -> Vec<u32> {
+    match Jeu::charger(chemin.to_string()).await {
+        Ok(Some(Jeu { jeu: Some(id), .. })) => obtenir_plateformes(id).await,
+        _ => vec![],
+    }
+}
+
+async fn charger_surcharge(chemin: &str) -> Result<SurchargeLancement, Erreur> {
+    match SurchargeLancement::charger(chemin.to_string()).await {
+        Ok(Some(surcharge)) => Ok(surcharge),
+        Ok(None) => Ok(SurchargeLancement::vide(chemin.to_string())),
+        Err(erreur) => ErreurSurcharge { erreur, chemin: chemin.to_string() }.as_err(),
+    }
+}
+
+pub async fn lister_emulateurs(chemin: &str) -> Vec<Emulateur> {
+    let config = obtenir_config();
+    let plateformes = plateformes_jeu(chemin).await;
+
+    emulateurs_compatibles(&config, chemin, &plateformes)
+}
+
+pub async fn memoriser_emulateur(chemin: &str, emulateur: Option<String>) -> Result<(), Erreur> {
+    let mut surcharge = charger_surcharge(chemin).await?;
+    surcharge.emulateur = emulateur;
+
+    let resultat = match surcharge.est_vide() {
+        true => SurchargeLancement::supprimer(chemin.to_string()).await,
+        false => surcharge.enregistrer().await,
+    };
+
+    match resultat {
+        Ok(_) => Ok(()),
+        Err(erreur) => ErreurSurcharge { erreur, chemin: chemin.to_string() }.as_err(),
+    }
+}
+
+pub async fn obtenir_commande(chemin: &str, surcharge: &SurchargeLancement) -> Result<Resolution, Erreur> {
+    let config = obtenir_config();
+
+    if let Some(nom) = &surcharge.emulateur {
+        match trouver_emulateur(&config, nom) {
+            Some(emulateur) => return Ok(Resolution {
+                emulateur,
+                regle: Regle::Catalogue(nom.clone()),
+            }),
+            None => println!("ATTENTION: L'émulateur {} choisi pour {} n'existe plus.", nom, chemin),
+        }
+    }
+
+    let plateformes = plateformes_jeu(chemin).await;
+    let compatibles = emulateurs_compatibles(&config, chemin, &plateformes);
+
+    for defaut in &config.emulateurs_plateforme {
+        if !plateformes.contains(&defaut.plateforme) {
+            continue;
+        }
+
+        if let Some(emulateur) = compatibles.iter().find(|emulateur| emulateur.nom == defaut.emulateur) {
+            return Ok(Resolution {
+                emulateur: emulateur.clone(),
+                regle: Regle::Plateforme(defaut.plateforme, defaut.emulateur.clone()),
+            });
+        }
+    }
+
+    if let Some(emulateur) = compatibles.first() {
+        let regle = match emulateur.type_emulateur {
+            TypeEmulateur::Natif => Regle::Natif(emulateur.nom.clone()),
+            _ => Regle::Extension(
+                extension_compatible(&config, emulateur, chemin).unwrap_or_default(),
+                emulateur.nom.clone(),
+            ),
+        };
+
+        return Ok(Resolution {
+            emulateur: emulateur.clone(),
+            regle,
+        });
+    }
+
+    ErreurAucunEmulateur { chemin: chemin.to_string() }.as_err()
+}
+
+pub fn citer(texte: &str) -> String {
+    format!("'{}'", texte.replace("'", "'\\''"))
+}
+
+fn modele_commande(emulateur: &Emulateur, chemin: &str) -> String {
+    let config = obtenir_config();
+
+    match emulateur.type_emulateur {
+        TypeEmulateur::Commande => emulateur.commande.clone(),
+        TypeEmulateur::RetroArch => retroarch::modele_commande(emulateur),
+        TypeEmulateur::Natif | TypeEmulateur::AppImage => String::from("{chemin} {arguments}"),
+        TypeEmulateur::Desktop => {
+            match natif::lire_desktop(Path::new(chemin)).and_then(|entree| entree.exec) {
+                Some(exec) => format!("{} {{arguments}}", natif::commande_desktop(&exec)),
+                None => String::from("gio launch {chemin} {arguments}"),
+            }
+        },
+        TypeEmulateur::Wine => match config.wine.and_then(|wine| wine.prefixe) {
+            Some(prefixe) => format!(
+                "WINEPREFIX={} {} {{chemin}} {{arguments}}",
+                citer(&prefixe.display().to_string()),
+                emulateur.commande,
+            ),
+            None => format!("{} {{chemin}} {{arguments}}", emulateur.commande),
+        },
+        TypeEmulateur::Proton => {
+            let mut modele = String::new();
+
+            if let Some(proton) = config.proton {
+                modele.push_str(&format!(
+                    "STEAM_COMPAT_DATA_PATH={} ",
+                    citer(&proton.prefixe.display().to_string()),
+                ));
+
+                let steam = proton.steam.or(dirs::home_dir().map(|dossier| dossier.join(".steam/steam")));
+                if let Some(steam) = steam {
+                    modele.push_str(&format!(
+                        "STEAM_COMPAT_CLIENT_INSTALL_PATH={} ",
+                        citer(&steam.display().to_string()),
+                    ));
+                }
+            }
+
+            format!("{}{} run {{chemin}} {{arguments}}", modele, citer(&emulateur.commande))
+        },
+    }
+}
+
+// Répertoire de travail par défaut: celui du raccourci ou du jeu lui-même.
+fn repertoire_defaut(emulateur: &Emulateur, chemin: &str) -> Option<String> {
+    let desktop = match emulateur.type_emulateur {
+        TypeEmulateur::Desktop => natif::lire_desktop(Path::new(chemin)).and_then(|entree| entree.repertoire),
+        _ => None,
+    };
+
+    match emulateur.type_emulateur {
+        TypeEmulateur::Commande | TypeEmulateur::RetroArch => None,
+        _ => desktop.or(Path::new(chemin).parent().map(|dossier| dossier.display().to_string())),
+    }
+}
+
+fn creer_commande(modele: String, chemin: &str, arguments: &Option<String>) -> String {
+    let mut commande = modele;
+    let arguments_str = arguments.clone().unwrap_or_default();
+
+    commande = commande.replace("{{chemin}}", "{{/!\\-*-tmp-*-/!\\}}");
+    commande = commande.replace("{chemin}", citer(chemin).as_str());
+    commande = commande.replace("{{/!\\-*-tmp-*-/!\\}}", "{{chemin}}");
+
+    if commande.contains("{arguments}") {
+        commande = commande.replace("{arguments}", arguments_str.as_str());
+    } else if !arguments_str.is_empty() {
+        commande = format!("{} {}", commande, arguments_str);
+    }
+
+    commande
+}
+
+async fn variables_jeu(chemin: &str, emulateur: &Emulateur, surcharge: &SurchargeLancement) -> Vec<(String, String)> {
+    let mut variables: Vec<(String, String)> = vec![
+        (String::from("LUDOTHEQUE_CHEMIN"), chemin.to_string()),
+        (String::from("LUDOTHEQUE_EMULATEUR"), emulateur.nom.clone()),
+    ];
+
+    if let Ok(Some(jeu)) = Jeu::charger(chemin.to_string()).await {
+        variables.push((String::from("LUDOTHEQUE_NOM"), jeu.nom));
+        variables.push((String::from("LUDOTHEQUE_LANGUE"), jeu.langue));
+
+        if let Some(id) = jeu.jeu {
+            let plateformes: Vec<String> = obtenir_plateformes(id)
+                .await
+                .iter()
+                .map(|plateforme| plateforme.to_string())
+                .collect();
+
+            variables.push((String::from("LUDOTHEQUE_IGDB_ID"), id.to_string()));
+            variables.push((String::from("LUDOTHEQUE_PLATEFORMES"), plateformes.join(",")));
+        }
+    }
+
+    variables.extend(surcharge.variables());
+
+    variables
+}
+
+#[cfg(target_os = "linux")]
+fn appeler_commande(commande: String, surcharge: &SurchargeLancement, repertoire: Option<String>) -> Result<Child, Erreur> {
+    let mut processus = Command::new("sh");
+    processus
+        .arg("-c")
+        .arg(&commande)
+        .envs(surcharge.variables());
+
+    if let Some(repertoire) = surcharge.repertoire.clone().or(repertoire) {
+        processus.current_dir(repertoire);
+    }
+
+    match processus.spawn() {
+        Ok(enfant) => Ok(enfant),
+        Err(erreur) => ErreurLancement { erreur, commande }.as_err(),
+    }
+}
+
+// Lance le jeu et renvoie le processus avec les crochets à exécuter à sa fin.
+async fn demarrer_jeu(chemin: String) -> Result<(Child, Vec<Crochet>, Vec<(String, String)>), Erreur> {
+    let config = obtenir_config();
+    let surcharge = charger_surcharge(&chemin).await?;
+    let resolution = obtenir_commande(&chemin, &surcharge).await?;
+
+    println!("INFO: {}", resolution.regle);
+
+    let commande = creer_commande(modele_commande(&resolution.emulateur, &chemin), &chemin, &surcharge.arguments);
+    let repertoire = repertoire_defaut(&resolution.emulateur, &chemin);
+
+    let mut variables = variables_jeu(&chemin, &resolution.emulateur, &surcharge).await;
+    variables.push((String::from("LUDOTHEQUE_COMMANDE"), commande.clone()));
+
+    let crochets_jeu = surcharge.crochets();
+
+    let avant: Vec<Crochet> = [
+        config.crochets.avant,
+        resolution.emulateur.crochets.avant.clone(),
+        crochets_jeu.avant,
+    ].concat();
+    let apres: Vec<Crochet> = [
+        crochets_jeu.apres,
+        resolution.emulateur.crochets.apres.clone(),
+        config.crochets.apres,
+    ].concat();
+
+    // Les crochets peuvent durer jusqu'à leur délai: ils bloquent ce fil, pas l'exécuteur.
+    tokio::task::block_in_place(|| executer_crochets_avant(&avant, &variables))?;
+
+    match appeler_commande(commande, &surcharge, repertoire) {
+        Ok(processus) => {
+            parties::noter_partie(&chemin).await;
+            Ok((processus, apres, variables))
+        },
+        Err(erreur) => {
+            tokio::task::block_in_place(|| executer_crochets_apres(&apres, &variables));
+            Err(erreur)
+        },
+    }
+}
+
+fn attendre_fin(mut processus: Child, apres: &[Crochet], variables: &[(String, String)]) {
+    if let Err(erreur) = processus.wait() {
+        println!("ATTENTION: Impossible d'attendre la fin du jeu: {}", erreur);
+    }
+
+    executer_crochets_apres(apres, variables);
+}
+
+pub async fn lancer_jeu_async(chemin: String) -> Result<(), Erreur> {
+    let (processus, apres, variables) = demarrer_jeu(chemin).await?;
+
+    std::thread::spawn(move || attendre_fin(processus, &apres, &variables));
+
+    Ok(())
+}
+
+// Depuis un autre lanceur (ex: Steam), qui suit le jeu tant que la ludothèque tourne.
+pub async fn lancer_jeu_et_attendre(chemin: String) -> Result<(), Erreur> {
+    let (processus, apres, variables) = demarrer_jeu(chemin).await?;
+    tokio::task::block_in_place(|| attendre_fin(processus, &apres, &variables));
+    Ok(())
+}
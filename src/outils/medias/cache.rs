@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sqlx::FromRow;
+
+use crate::chemin::chemins;
+use crate::donnees::config::obtenir_config;
+use crate::donnees::igdb::obtenir_db;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::arriere_plan::Suivi;
+use crate::outils::medias::{telecharger_medias, Media, TypeMedia};
+use crate::outils::medias::err::*;
+
+const MO: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, FromRow)]
+struct EntreeCache {
+    chemin: String,
+    type_media: String,
+    image_id: String,
+    taille: i64,
+}
+
+fn maintenant() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duree| duree.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+pub async fn noter_acces(media: &Media, chemin: &Path) {
+    let taille = match std::fs::metadata(chemin) {
+        Ok(valeur) => valeur.len() as i64,
+        Err(_) => return,
+    };
+
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return erreur.afficher_attention(),
+    };
+
+    if let Err(erreur) = sqlx::query(
+        "INSERT INTO cache_medias (chemin, type_media, image_id, taille, utilise_le)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (chemin) DO UPDATE SET taille = excluded.taille, utilise_le = excluded.utilise_le;"
+    )
+        .bind(chemin.display().to_string())
+        .bind(media.type_media.dossier())
+        .bind(&media.image_id)
+        .bind(taille)
+        .bind(maintenant())
+        .execute(&db).await {
+        println!("ATTENTION: {}", erreur);
+    }
+}
+
+// Couvertures des jeux du catalogue, qui ne sont jamais évincées.
+async fn couvertures_protegees() -> HashSet<String> {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_attention();
+            return HashSet::new();
+        },
+    };
+
+    match sqlx::query_scalar::<_, String>(
+        "SELECT couvertures.url FROM catalogue
+        JOIN jeux ON catalogue.jeu = jeux.id
+        JOIN couvertures ON jeux.cover = couvertures.id;"
+    ).fetch_all(&db).await {
+        Ok(liste) => liste
+            .iter()
+            .filter_map(|url| Media::depuis_url(TypeMedia::Couverture, url))
+            .map(|media| media.image_id)
+            .collect(),
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            HashSet::new()
+        },
+    }
+}
+
+// Évince les médias les moins récemment utilisés jusqu'à respecter le budget.
+pub async fn appliquer_budget() {
+    let budget = (obtenir_config().cache_medias.budget * MO) as i64;
+
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return erreur.afficher_attention(),
+    };
+
+    let entrees = match sqlx::query_as::<_, EntreeCache>(
+        "SELECT chemin, type_media, image_id, taille FROM cache_medias ORDER BY utilise_le ASC;"
+    ).fetch_all(&db).await {
+        Ok(liste) => liste,
+        Err(erreur) => return println!("ATTENTION: {}", erreur),
+    };
+
+    let mut total: i64 = entrees.iter().map(|entree| entree.taille).sum();
+    if total <= budget {
+        return;
+    }
+
+    let protegees = couvertures_protegees().await;
+    let mut evinces = 0;
+
+    for entree in entrees {
+        if total <= budget {
+            break;
+        }
+
+        if entree.type_media == TypeMedia::Couverture.dossier() && protegees.contains(&entree.image_id) {
+            continue;
+        }
+
+        match std::fs::remove_file(&entree.chemin) {
+            Ok(_) => {},
+            Err(erreur) if erreur.kind() == std::io::ErrorKind::NotFound => {},
+            Err(erreur) => {
+                println!("ATTENTION: Impossible de supprimer {}: {}", entree.chemin, erreur);
+                continue;
+            },
+        }
+
+        if let Err(erreur) = sqlx::query("DELETE FROM cache_medias WHERE chemin = ?;")
+            .bind(&entree.chemin)
+            .execute(&db).await {
+            println!("ATTENTION: {}", erreur);
+        }
+
+        total -= entree.taille;
+        evinces += 1;
+    }
+
+    println!("INFO: {} média(s) évincé(s) du cache, {:.1} Mo utilisés.", evinces, total as f64 / MO as f64);
+}
+
+pub async fn vider(type_media: TypeMedia) -> Result<(), Erreur> {
+    match chemins::determiner_chemin(format!("medias/{}", type_media.dossier()), chemins::XDG::CACHE) {
+        Ok(dossier) => match std::fs::remove_dir_all(&dossier) {
+            Ok(_) => println!("INFO: {} vidé.", dossier.display()),
+            Err(erreur) if erreur.kind() == std::io::ErrorKind::NotFound => {},
+            Err(erreur) => return ErreurVidage { erreur, dossier }.as_err(),
+        },
+        Err(erreur) => {
+            erreur.afficher_attention();
+            return Ok(());
+        },
+    }
+
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_attention();
+            return Ok(());
+        },
+    };
+
+    if let Err(erreur) = sqlx::query("DELETE FROM cache_medias WHERE type_media = ?;")
+        .bind(type_media.dossier())
+        .execute(&db).await {
+        println!("ATTENTION: {}", erreur);
+    }
+
+    Ok(())
+}
+
+fn occupation(dossier: &Path) -> (u64, u64) {
+    let entrees = match std::fs::read_dir(dossier) {
+        Ok(valeur) => valeur,
+        Err(_) => return (0, 0),
+    };
+
+    let mut fichiers = 0;
+    let mut octets = 0;
+
+    for entree in entrees.filter_map(|entree| entree.ok()) {
+        let chemin = entree.path();
+
+        if chemin.is_dir() {
+            let (sous_fichiers, sous_octets) = occupation(&chemin);
+            fichiers += sous_fichiers;
+            octets += sous_octets;
+        } else if let Ok(metadonnees) = entree.metadata() {
+            fichiers += 1;
+            octets += metadonnees.len();
+        }
+    }
+
+    (fichiers, octets)
+}
+
+pub fn rapport() {
+    let mut total = 0;
+
+    for type_media in TypeMedia::tous() {
+        let (fichiers, octets) = match chemins::determiner_chemin(format!("medias/{}", type_media.dossier()), chemins::XDG::CACHE) {
+            Ok(dossier) => occupation(&dossier),
+            Err(erreur) => {
+                erreur.afficher_attention();
+                continue;
+            },
+        };
+
+        total += octets;
+        println!("{:<20} {:>6} fichier(s) {:>10.1} Mo", type_media.dossier(), fichiers, octets as f64 / MO as f64);
+    }
+
+    println!("{:<20} {:>28.1} Mo (budget: {} Mo)", "total", total as f64 / MO as f64, obtenir_config().cache_medias.budget);
+}
+
+fn ou_tous(types: &[TypeMedia]) -> Vec<TypeMedia> {
+    match types.is_empty() {
+        true => TypeMedia::tous(),
+        false => types.to_vec(),
+    }
+}
+
+// Sans type, tous les médias sont concernés.
+pub async fn vider_types(types: &[TypeMedia]) -> Result<(), Erreur> {
+    for type_media in ou_tous(types) {
+        vider(type_media).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn retelecharger(types: &[TypeMedia]) -> Result<(), Erreur> {
+    let types = ou_tous(types);
+
+    vider_types(&types).await?;
+    telecharger_medias(&types, &Suivi::default()).await;
+
+    Ok(())
+}
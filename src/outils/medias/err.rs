@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use crate::interne::erreurs::TraitErreur;
+
+// Erreur TypeInconnu
+pub struct ErreurTypeInconnu {
+    pub type_media: String,
+    pub types: Vec<&'static str>,
+}
+
+impl ErreurTypeInconnu {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurTypeInconnu(self))
+    }
+}
+
+impl TraitErreur for ErreurTypeInconnu {
+    fn message(&self) -> String {
+        format!("Type de média inconnu: {}.", self.type_media)
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("Types: {}", self.types.join(", ")))
+    }
+}
+
+// Erreur Vidage
+pub struct ErreurVidage {
+    pub erreur: std::io::Error,
+    pub dossier: PathBuf,
+}
+
+impl ErreurVidage {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurVidage(self))
+    }
+}
+
+impl TraitErreur for ErreurVidage {
+    fn message(&self) -> String {
+        format!("Impossible de vider {}.", self.dossier.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurTypeInconnu(ErreurTypeInconnu),
+    ErreurVidage(ErreurVidage),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurTypeInconnu(erreur) => erreur,
+            Erreur::ErreurVidage(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
@@ -0,0 +1,301 @@
+pub mod cache;
+pub mod err;
+
+use std::path::PathBuf;
+
+use lazy_static::lazy_static;
+
+use crate::api::client_http;
+use crate::chemin::chemins;
+use crate::donnees::config::{self, ConfigServeur};
+use crate::donnees::igdb::extra::{obtenir_catalogue, obtenir_entreprises};
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::*;
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::arriere_plan::Suivi;
+use crate::outils::dl::{self, Bilan, Telechargement};
+
+lazy_static! {
+    static ref SERVEUR_MEDIAS: ConfigServeur = config::obtenir_config().serveurs.medias;
+    pub static ref CLIENT_MEDIAS: reqwest::Client = client_http(&SERVEUR_MEDIAS);
+}
+
+// En ligne de commande, les types portent le nom de leur dossier.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum TypeMedia {
+    #[value(name = "couvertures")]
+    Couverture,
+    #[value(name = "illustrations")]
+    Illustration,
+    #[value(name = "captures")]
+    CaptureEcran,
+    #[value(name = "logos_plateforme")]
+    LogoPlateforme,
+    #[value(name = "logos_entreprise")]
+    LogoEntreprise,
+}
+
+impl TypeMedia {
+    pub fn tous() -> Vec<TypeMedia> {
+        vec![
+            TypeMedia::Couverture,
+            TypeMedia::Illustration,
+            TypeMedia::CaptureEcran,
+            TypeMedia::LogoPlateforme,
+            TypeMedia::LogoEntreprise,
+        ]
+    }
+
+    pub fn dossier(&self) -> &'static str {
+        match self {
+            TypeMedia::Couverture => "couvertures",
+            TypeMedia::Illustration => "illustrations",
+            TypeMedia::CaptureEcran => "captures",
+            TypeMedia::LogoPlateforme => "logos_plateforme",
+            TypeMedia::LogoEntreprise => "logos_entreprise",
+        }
+    }
+
+    pub fn depuis_dossier(dossier: &str) -> Option<TypeMedia> {
+        TypeMedia::tous().into_iter().find(|type_media| type_media.dossier() == dossier)
+    }
+
+    pub fn taille_defaut(&self) -> TailleImage {
+        match self {
+            TypeMedia::Couverture => TailleImage::CouvertureGrande,
+            TypeMedia::Illustration | TypeMedia::CaptureEcran => TailleImage::CaptureGrande,
+            TypeMedia::LogoPlateforme | TypeMedia::LogoEntreprise => TailleImage::Miniature,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TailleImage {
+    Miniature,
+    CouvertureGrande,
+    CaptureGrande,
+    HD,
+}
+
+impl TailleImage {
+    pub fn code(&self) -> &'static str {
+        match self {
+            TailleImage::Miniature => "t_thumb",
+            TailleImage::CouvertureGrande => "t_cover_big",
+            TailleImage::CaptureGrande => "t_screenshot_big",
+            TailleImage::HD => "t_1080p",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Media {
+    pub type_media: TypeMedia,
+    pub image_id: String,
+}
+
+impl Media {
+    // Les URL IGDB ont la forme //images.igdb.com/igdb/image/upload/t_thumb/<image_id>.jpg
+    pub fn depuis_url(type_media: TypeMedia, url: &str) -> Option<Media> {
+        let fichier = url.rsplit('/').next()?;
+        let image_id = fichier.split('.').next()?;
+
+        match image_id.is_empty() {
+            true => None,
+            false => Some(Media { type_media, image_id: String::from(image_id) }),
+        }
+    }
+
+    pub fn url(&self, taille: TailleImage) -> String {
+        SERVEUR_MEDIAS.url(&format!("{}/{}.jpg", taille.code(), self.image_id))
+    }
+
+    pub fn nom_cache(&self, taille: TailleImage) -> String {
+        format!("medias/{}/{}/{}.jpg", self.type_media.dossier(), taille.code(), self.image_id)
+    }
+
+    pub fn chemin(&self, taille: TailleImage) -> Result<PathBuf, chemins::err::Erreur> {
+        chemins::determiner_chemin(self.nom_cache(taille), chemins::XDG::CACHE)
+    }
+
+    // Chemin local si l'image est déjà en cache, sans rien télécharger.
+    pub fn chemin_local(&self, taille: TailleImage) -> Option<PathBuf> {
+        chemins::trouver_chemin(self.nom_cache(taille), chemins::XDG::CACHE).ok()
+    }
+
+    pub fn telechargement(&self, taille: TailleImage) -> Option<Telechargement> {
+        match self.chemin(taille) {
+            Ok(destination) => Some(Telechargement { url: self.url(taille), destination }),
+            Err(erreur) => {
+                erreur.afficher_attention();
+                None
+            },
+        }
+    }
+
+    // Renvoie le chemin local de l'image, en la téléchargeant au besoin.
+    pub async fn obtenir(&self, taille: TailleImage) -> Option<PathBuf> {
+        if let Some(chemin) = self.chemin_local(taille) {
+            cache::noter_acces(self, &chemin).await;
+            return Some(chemin);
+        }
+
+        let tache = self.telechargement(taille)?;
+
+        match dl::telecharger_fichier(&CLIENT_MEDIAS, &tache.url, &tache.destination).await {
+            Ok(_) => {
+                cache::noter_acces(self, &tache.destination).await;
+                Some(tache.destination)
+            },
+            Err(erreur) => {
+                erreur.afficher_attention();
+                None
+            },
+        }
+    }
+}
+
+// Couverture IGDB d'un jeu, rangée avec les autres médias dans couvertures/.
+pub fn couverture(jeu: &JeuIGDB) -> Option<Media> {
+    jeu.cover.as_ref().and_then(|cover| Media::depuis_url(TypeMedia::Couverture, &cover.url))
+}
+
+// Couverture d'un jeu IGDB déjà en cache, sans rien télécharger.
+pub async fn couverture_locale(id: u32) -> Option<PathBuf> {
+    let jeu = JeuIGDB::charger(id).await.ok().flatten()?;
+    couverture(&jeu)?.chemin_local(TailleImage::CouvertureGrande)
+}
+
+fn ajouter(medias: &mut Vec<Media>, type_media: TypeMedia, url: &str) {
+    if let Some(media) = Media::depuis_url(type_media, url) {
+        medias.push(media);
+    }
+}
+
+pub async fn medias_jeu(jeu: &JeuIGDB) -> Vec<Media> {
+    let mut medias: Vec<Media> = vec![];
+
+    if let Some(couverture) = &jeu.cover {
+        ajouter(&mut medias, TypeMedia::Couverture, &couverture.url);
+    }
+
+    for illustration in jeu.artworks.clone().unwrap_or(vec![]) {
+        ajouter(&mut medias, TypeMedia::Illustration, &illustration.url);
+    }
+
+    for capture in jeu.screenshots.clone().unwrap_or(vec![]) {
+        ajouter(&mut medias, TypeMedia::CaptureEcran, &capture.url);
+    }
+
+    for plateforme in jeu.platforms.clone().unwrap_or(vec![]) {
+        if let Ok(Some(PlateformeIGDB { platform_logo: Some(logo), .. })) = PlateformeIGDB::charger(plateforme).await {
+            ajouter(&mut medias, TypeMedia::LogoPlateforme, &logo.url);
+        }
+    }
+
+    for entreprise in obtenir_entreprises(jeu.id).await {
+        if let Ok(Some(EntrepriseIGDB { logo: Some(logo), .. })) = EntrepriseIGDB::charger(entreprise).await {
+            ajouter(&mut medias, TypeMedia::LogoEntreprise, &logo.url);
+        }
+    }
+
+    medias
+}
+
+// Image fournie par un fournisseur de métadonnées.
+#[derive(Debug, Clone)]
+pub enum SourceMedia {
+    // Image IGDB, téléchargée à la demande dans le cache.
+    Distant(Media),
+    // Image déjà sur le disque, ex: citée par une fiche locale.
+    Local(TypeMedia, PathBuf),
+}
+
+impl SourceMedia {
+    pub fn type_media(&self) -> TypeMedia {
+        match self {
+            SourceMedia::Distant(media) => media.type_media,
+            SourceMedia::Local(type_media, _) => *type_media,
+        }
+    }
+
+    pub async fn obtenir(&self, taille: TailleImage) -> Option<PathBuf> {
+        match self {
+            SourceMedia::Distant(media) => media.obtenir(taille).await,
+            SourceMedia::Local(_, chemin) => Some(chemin.clone()).filter(|chemin| chemin.is_file()),
+        }
+    }
+
+    // Sans téléchargement: l'image distante doit déjà être en cache.
+    pub fn chemin_local(&self, taille: TailleImage) -> Option<PathBuf> {
+        match self {
+            SourceMedia::Distant(media) => media.chemin_local(taille),
+            SourceMedia::Local(_, chemin) => Some(chemin.clone()).filter(|chemin| chemin.is_file()),
+        }
+    }
+}
+
+// Galerie d'un jeu: illustrations et captures, téléchargées au besoin.
+pub async fn galerie(sources: Vec<SourceMedia>, taille: TailleImage) -> Vec<(SourceMedia, PathBuf)> {
+    let mut chemins: Vec<(SourceMedia, PathBuf)> = vec![];
+
+    for source in sources {
+        if source.type_media() != TypeMedia::Illustration && source.type_media() != TypeMedia::CaptureEcran {
+            continue;
+        }
+
+        if let Some(chemin) = source.obtenir(taille).await {
+            chemins.push((source, chemin));
+        }
+    }
+
+    cache::appliquer_budget().await;
+
+    chemins
+}
+
+pub async fn medias_catalogue(types: &[TypeMedia]) -> Vec<Media> {
+    let mut medias: Vec<Media> = vec![];
+
+    for jeu in obtenir_catalogue().await {
+        let jeu_igdb = match jeu.jeu {
+            Some(id) => match JeuIGDB::charger(id).await {
+                Ok(Some(valeur)) => valeur,
+                _ => continue,
+            },
+            None => continue,
+        };
+
+        for media in medias_jeu(&jeu_igdb).await {
+            let deja_vu = medias.iter().any(|autre| autre.type_media == media.type_media && autre.image_id == media.image_id);
+            if types.contains(&media.type_media) && !deja_vu {
+                medias.push(media);
+            }
+        }
+    }
+
+    medias
+}
+
+// Télécharge, à leur taille par défaut, les médias des jeux du catalogue.
+pub async fn telecharger_medias(types: &[TypeMedia], suivi: &Suivi) -> Bilan {
+    let medias = medias_catalogue(types).await;
+
+    let taches: Vec<Telechargement> = medias
+        .iter()
+        .filter_map(|media| media.telechargement(media.type_media.taille_defaut()))
+        .collect();
+
+    let bilan = dl::telecharger(taches, suivi).await;
+    bilan.afficher();
+
+    for media in &medias {
+        if let Some(chemin) = media.chemin_local(media.type_media.taille_defaut()) {
+            cache::noter_acces(media, &chemin).await;
+        }
+    }
+
+    cache::appliquer_budget().await;
+
+    bilan
+}
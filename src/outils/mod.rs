@@ -1,4 +1,19 @@
+pub mod accueil;
+pub mod arriere_plan;
+pub mod bureau;
+pub mod detection;
 pub mod dl;
+pub mod export;
+pub mod favoris;
+pub mod fournisseurs;
+pub mod images_perso;
+pub mod import;
 pub mod lanceur;
+pub mod medias;
+pub mod natif;
+pub mod parties;
+pub mod retroarch;
 pub mod scan;
+pub mod steam;
+pub mod synchro;
 pub mod trad;
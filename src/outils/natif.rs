@@ -0,0 +1,97 @@
+use std::io::Read;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use crate::donnees::config::LudothequeConfig;
+
+pub struct EntreeDesktop {
+    pub nom: Option<String>,
+    pub exec: Option<String>,
+    pub repertoire: Option<String>,
+}
+
+pub fn est_executable_natif(chemin: &Path) -> bool {
+    let metadonnees = match std::fs::metadata(chemin) {
+        Ok(valeur) => valeur,
+        Err(_) => return false,
+    };
+
+    if !metadonnees.is_file() || metadonnees.permissions().mode() & 0o111 == 0 {
+        return false;
+    }
+
+    let mut entete = [0u8; 4];
+    match std::fs::File::open(chemin).and_then(|mut fichier| fichier.read_exact(&mut entete)) {
+        Ok(_) => &entete == b"\x7fELF" || entete.starts_with(b"#!"),
+        Err(_) => false,
+    }
+}
+
+// Un exécutable n'est un jeu que dans les dossiers natifs configurés.
+pub fn est_jeu_natif(config: &LudothequeConfig, chemin: &Path) -> bool {
+    config.jeux_natifs
+        && config.dossiers_natifs.iter().any(|dossier| chemin.starts_with(dossier))
+        && est_executable_natif(chemin)
+}
+
+pub fn lire_desktop(chemin: &Path) -> Option<EntreeDesktop> {
+    let contenu = match std::fs::read_to_string(chemin) {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            println!("ATTENTION: {}: {}", chemin.display(), erreur);
+            return None;
+        },
+    };
+
+    let mut entree = EntreeDesktop { nom: None, exec: None, repertoire: None };
+    let mut groupe_principal = false;
+
+    for ligne in contenu.lines() {
+        let ligne = ligne.trim();
+
+        if ligne.starts_with('[') {
+            groupe_principal = ligne == "[Desktop Entry]";
+            continue;
+        }
+
+        if !groupe_principal {
+            continue;
+        }
+
+        match ligne.split_once('=') {
+            Some(("Name", valeur)) => entree.nom = Some(String::from(valeur.trim())),
+            Some(("Exec", valeur)) => entree.exec = Some(String::from(valeur.trim())),
+            Some(("Path", valeur)) => entree.repertoire = Some(String::from(valeur.trim())),
+            _ => {},
+        }
+    }
+
+    Some(entree)
+}
+
+// Retire les codes de champ (%f, %U, ...) d'une ligne Exec.
+pub fn commande_desktop(exec: &str) -> String {
+    let mut commande = String::new();
+    let mut caracteres = exec.chars();
+
+    while let Some(caractere) = caracteres.next() {
+        if caractere != '%' {
+            commande.push(caractere);
+            continue;
+        }
+
+        if let Some('%') = caracteres.next() {
+            commande.push('%');
+        }
+    }
+
+    String::from(commande.trim())
+}
+
+// Nom affiché par un raccourci .desktop, utilisé pour l'identification.
+pub fn nom_raccourci(chemin: &Path) -> Option<String> {
+    match chemin.extension().and_then(|extension| extension.to_str()) {
+        Some("desktop") => lire_desktop(chemin).and_then(|entree| entree.nom),
+        _ => None,
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::donnees::igdb::obtenir_db;
+use crate::interne::erreurs::TraitErreur;
+
+// Date de la dernière partie et nombre de lancements, pour les jeux récemment joués.
+pub async fn noter_partie(chemin: &str) {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return erreur.afficher_attention(),
+    };
+
+    let maintenant = SystemTime::now().duration_since(UNIX_EPOCH).map(|duree| duree.as_secs() as i64).unwrap_or(0);
+
+    if let Err(erreur) = sqlx::query(
+        "INSERT INTO parties (chemin, jouee_le, nombre) VALUES (?, ?, 1)
+        ON CONFLICT (chemin) DO UPDATE SET jouee_le = excluded.jouee_le, nombre = nombre + 1;"
+    )
+        .bind(chemin)
+        .bind(maintenant)
+        .execute(&db).await {
+        println!("ATTENTION: {}", erreur);
+    }
+}
+
+pub async fn oublier_parties(chemin: &str) {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return erreur.afficher_attention(),
+    };
+
+    if let Err(erreur) = sqlx::query("DELETE FROM parties WHERE chemin = ?;").bind(chemin).execute(&db).await {
+        println!("ATTENTION: {}", erreur);
+    }
+}
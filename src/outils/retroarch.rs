@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::donnees::config::{self, ConfigRetroArch, Crochets, Emulateur, TypeEmulateur};
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::lanceur::{citer, memoriser_emulateur};
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::Jeu;
+use crate::outils::arriere_plan::Suivi;
+use crate::outils::scan::identifier_jeux;
+
+// Correspondance entre les bases de données libretro et les plateformes IGDB.
+const PLATEFORMES: &[(&str, u32)] = &[
+    ("Nintendo - Nintendo Entertainment System", 18),
+    ("Nintendo - Family Computer Disk System", 51),
+    ("Nintendo - Super Nintendo Entertainment System", 19),
+    ("Nintendo - Nintendo 64", 4),
+    ("Nintendo - GameCube", 21),
+    ("Nintendo - Wii", 5),
+    ("Nintendo - Game Boy", 33),
+    ("Nintendo - Game Boy Color", 22),
+    ("Nintendo - Game Boy Advance", 24),
+    ("Nintendo - Nintendo DS", 20),
+    ("Nintendo - Nintendo 3DS", 37),
+    ("Nintendo - Virtual Boy", 87),
+    ("Nintendo - Pokemon Mini", 166),
+    ("Sega - SG-1000", 84),
+    ("Sega - Master System - Mark III", 64),
+    ("Sega - Mega Drive - Genesis", 29),
+    ("Sega - 32X", 30),
+    ("Sega - Mega-CD - Sega CD", 78),
+    ("Sega - Game Gear", 35),
+    ("Sega - Saturn", 32),
+    ("Sega - Dreamcast", 23),
+    ("Sony - PlayStation", 7),
+    ("Sony - PlayStation 2", 8),
+    ("Sony - PlayStation Portable", 38),
+    ("Atari - 2600", 59),
+    ("Atari - 5200", 66),
+    ("Atari - 7800", 60),
+    ("Atari - Jaguar", 62),
+    ("Atari - Lynx", 61),
+    ("NEC - PC Engine - TurboGrafx 16", 86),
+    ("NEC - PC-FX", 274),
+    ("SNK - Neo Geo Pocket", 119),
+    ("SNK - Neo Geo Pocket Color", 120),
+    ("SNK - Neo Geo", 80),
+    ("Bandai - WonderSwan", 57),
+    ("Bandai - WonderSwan Color", 123),
+    ("The 3DO Company - 3DO", 50),
+    ("GCE - Vectrex", 70),
+    ("Coleco - ColecoVision", 68),
+    ("Mattel - Intellivision", 67),
+    ("Microsoft - MSX", 27),
+    ("Commodore - 64", 15),
+    ("Commodore - Amiga", 16),
+    ("DOS", 13),
+    ("FBNeo - Arcade Games", 52),
+    ("MAME", 52),
+];
+
+pub struct CoeurRetroArch {
+    pub nom: String,
+    pub fichier: PathBuf,
+    pub extensions: Vec<String>,
+    pub plateformes: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ListeRetroArch {
+    items: Vec<ElementListeRetroArch>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ElementListeRetroArch {
+    path: String,
+    core_path: Option<String>,
+}
+
+fn repertoire_retroarch() -> Option<PathBuf> {
+    dirs::config_dir().map(|dossier| dossier.join("retroarch"))
+}
+
+fn repertoire_flatpak() -> Option<PathBuf> {
+    dirs::home_dir().map(|dossier| dossier.join(".var/app/org.libretro.RetroArch/config/retroarch"))
+}
+
+fn repertoires_coeurs(config: &ConfigRetroArch) -> Vec<PathBuf> {
+    let mut repertoires: Vec<PathBuf> = vec![];
+
+    if let Some(repertoire) = &config.repertoire_coeurs {
+        repertoires.push(repertoire.clone());
+    }
+    if let Some(repertoire) = repertoire_retroarch() {
+        repertoires.push(repertoire.join("cores"));
+    }
+    if let Some(repertoire) = repertoire_flatpak() {
+        repertoires.push(repertoire.join("cores"));
+    }
+    repertoires.push(PathBuf::from("/usr/lib/libretro"));
+    repertoires.push(PathBuf::from("/usr/lib64/libretro"));
+    repertoires.push(PathBuf::from("/usr/lib/x86_64-linux-gnu/libretro"));
+
+    repertoires
+}
+
+fn repertoires_infos(config: &ConfigRetroArch) -> Vec<PathBuf> {
+    let mut repertoires: Vec<PathBuf> = vec![];
+
+    if let Some(repertoire) = &config.repertoire_infos {
+        repertoires.push(repertoire.clone());
+    }
+    if let Some(repertoire) = repertoire_retroarch() {
+        repertoires.push(repertoire.join("info"));
+        repertoires.push(repertoire.join("cores"));
+    }
+    if let Some(repertoire) = repertoire_flatpak() {
+        repertoires.push(repertoire.join("info"));
+        repertoires.push(repertoire.join("cores"));
+    }
+    repertoires.push(PathBuf::from("/usr/share/libretro/info"));
+
+    repertoires
+}
+
+fn fichiers(dossier: &Path, extension: &str) -> Vec<PathBuf> {
+    match std::fs::read_dir(dossier) {
+        Ok(valeur) => valeur
+            .filter_map(|entree| entree.ok())
+            .map(|entree| entree.path())
+            .filter(|chemin| chemin.extension().is_some_and(|ext| ext == extension))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+fn lire_info(chemin: &Path) -> HashMap<String, String> {
+    let mut valeurs: HashMap<String, String> = HashMap::new();
+
+    let contenu = match std::fs::read_to_string(chemin) {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            println!("ATTENTION: {}: {}", chemin.display(), erreur);
+            return valeurs;
+        },
+    };
+
+    for ligne in contenu.lines() {
+        if let Some((cle, valeur)) = ligne.split_once('=') {
+            valeurs.insert(
+                String::from(cle.trim()),
+                String::from(valeur.trim().trim_matches('"')),
+            );
+        }
+    }
+
+    valeurs
+}
+
+fn trouver_coeur(config: &ConfigRetroArch, nom_info: &str) -> Option<PathBuf> {
+    let nom = format!("{}.so", nom_info);
+
+    repertoires_coeurs(config)
+        .into_iter()
+        .map(|repertoire| repertoire.join(&nom))
+        .find(|chemin| chemin.exists())
+}
+
+pub fn plateformes_libretro(bases: &str) -> Vec<u32> {
+    let mut plateformes: Vec<u32> = vec![];
+
+    for base in bases.split('|') {
+        let base = base.trim().trim_end_matches(".lpl").trim_end_matches(".rdb");
+        for (nom, id) in PLATEFORMES {
+            if *nom == base && !plateformes.contains(id) {
+                plateformes.push(*id);
+            }
+        }
+    }
+
+    plateformes
+}
+
+// Base libretro d'une plateforme IGDB, qui nomme aussi les listes RetroArch.
+pub fn base_libretro(plateforme: u32) -> Option<&'static str> {
+    PLATEFORMES.iter()
+        .find(|(_, id)| *id == plateforme)
+        .map(|(nom, _)| *nom)
+}
+
+pub fn decouvrir_coeurs(config: &ConfigRetroArch) -> Vec<CoeurRetroArch> {
+    let mut coeurs: Vec<CoeurRetroArch> = vec![];
+
+    for repertoire in repertoires_infos(config) {
+        for info in fichiers(&repertoire, "info") {
+            let nom_info = match info.file_stem().and_then(|nom| nom.to_str()) {
+                Some(valeur) => String::from(valeur),
+                None => continue,
+            };
+
+            if coeurs.iter().any(|coeur| coeur.fichier.file_stem().and_then(|nom| nom.to_str()) == Some(nom_info.as_str())) {
+                continue;
+            }
+
+            let fichier = match trouver_coeur(config, &nom_info) {
+                Some(valeur) => valeur,
+                None => continue,
+            };
+
+            let valeurs = lire_info(&info);
+
+            let extensions: Vec<String> = valeurs.get("supported_extensions")
+                .map(|liste| liste.split('|').filter(|ext| !ext.is_empty()).map(String::from).collect())
+                .unwrap_or(vec![]);
+
+            if extensions.is_empty() {
+                continue;
+            }
+
+            coeurs.push(CoeurRetroArch {
+                nom: valeurs.get("display_name").cloned().unwrap_or(nom_info),
+                fichier,
+                extensions,
+                plateformes: plateformes_libretro(valeurs.get("database").map(|v| v.as_str()).unwrap_or("")),
+            });
+        }
+    }
+
+    coeurs
+}
+
+pub fn emulateur_retroarch(config: &ConfigRetroArch, coeur: &CoeurRetroArch) -> Emulateur {
+    Emulateur {
+        nom: format!("RetroArch ({})", coeur.nom),
+        commande: config.commande.clone(),
+        extensions: coeur.extensions.clone(),
+        priorite: 0,
+        plateformes: coeur.plateformes.clone(),
+        type_emulateur: TypeEmulateur::RetroArch,
+        coeur: Some(coeur.fichier.clone()),
+        crochets: Crochets::default(),
+    }
+}
+
+pub fn modele_commande(emulateur: &Emulateur) -> String {
+    match &emulateur.coeur {
+        Some(coeur) => format!(
+            "{} -L {} {{arguments}} {{chemin}}",
+            emulateur.commande,
+            citer(&coeur.display().to_string()),
+        ),
+        None => format!("{} {{arguments}} {{chemin}}", emulateur.commande),
+    }
+}
+
+pub fn synchroniser_coeurs() {
+    let mut config = config::obtenir_config();
+
+    let retroarch = match &config.retroarch {
+        Some(valeur) => valeur.clone(),
+        None => return,
+    };
+
+    let mut ajouts = 0;
+
+    for coeur in decouvrir_coeurs(&retroarch) {
+        let existe = config.emulateurs
+            .iter()
+            .any(|emulateur| emulateur.coeur.as_ref() == Some(&coeur.fichier));
+
+        if !existe {
+            println!("INFO: Cœur RetroArch {} ajouté.", coeur.nom);
+            config.emulateurs.push(emulateur_retroarch(&retroarch, &coeur));
+            ajouts += 1;
+        }
+    }
+
+    if ajouts > 0 {
+        if let Err(erreur) = config::enregistrer_config(config) {
+            erreur.afficher_erreur();
+        }
+    }
+}
+
+fn listes_retroarch() -> Vec<PathBuf> {
+    let mut listes: Vec<PathBuf> = vec![];
+
+    if let Some(repertoire) = repertoire_retroarch() {
+        listes.extend(fichiers(&repertoire.join("playlists"), "lpl"));
+    }
+    if let Some(repertoire) = repertoire_flatpak() {
+        listes.extend(fichiers(&repertoire.join("playlists"), "lpl"));
+    }
+
+    listes
+}
+
+pub async fn importer_liste(fichier: &Path, suivi: &Suivi) {
+    let config = config::obtenir_config();
+
+    let liste: ListeRetroArch = match std::fs::read_to_string(fichier)
+        .map_err(|erreur| erreur.to_string())
+        .and_then(|contenu| serde_json::from_str(&contenu).map_err(|erreur| erreur.to_string())) {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            println!("ATTENTION: Liste RetroArch {} illisible: {}", fichier.display(), erreur);
+            return;
+        },
+    };
+
+    let mut elements: Vec<(String, Option<String>)> = vec![];
+
+    for element in liste.items {
+        let chemin = match element.path.split_once('#') {
+            Some((archive, _)) => String::from(archive),
+            None => element.path.clone(),
+        };
+
+        if !Path::new(&chemin).exists() {
+            continue;
+        }
+
+        elements.push((chemin, element.core_path));
+    }
+
+    identifier_jeux(elements.iter().map(|(chemin, _)| PathBuf::from(chemin)).collect(), false, suivi).await;
+
+    for (chemin, coeur) in elements {
+        if !Jeu::existe(chemin.clone()).await.unwrap_or(false) {
+            continue;
+        }
+
+        let coeur = match coeur {
+            Some(valeur) if valeur != "DETECT" && !valeur.is_empty() => PathBuf::from(valeur),
+            _ => continue,
+        };
+
+        let emulateur = config.emulateurs
+            .iter()
+            .find(|emulateur| emulateur.coeur.as_ref() == Some(&coeur));
+
+        if let Some(emulateur) = emulateur {
+            if let Err(erreur) = memoriser_emulateur(&chemin, Some(emulateur.nom.clone())).await {
+                erreur.afficher_erreur();
+            }
+        }
+    }
+}
+
+pub async fn importer_listes(suivi: &Suivi) {
+    let config = config::obtenir_config();
+
+    match config.retroarch {
+        Some(retroarch) if retroarch.importer_listes => {},
+        _ => return,
+    }
+
+    for liste in listes_retroarch() {
+        println!("INFO: Import de la liste RetroArch {}.", liste.display());
+        importer_liste(&liste, suivi).await;
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{interne::erreurs::TraitErreur, api::{igdb::err::Erreur as ErreurIGDB, openai::err::Erreur as ErreurGPT}};
+use crate::{interne::erreurs::TraitErreur, api::openai::err::Erreur as ErreurGPT};
 
 // Erreur Identification
 pub struct ErreurIdentification {
@@ -23,27 +23,6 @@ impl TraitErreur for ErreurIdentification {
     }
 }
 
-// Erreur Identification IGDB
-pub struct ErreurIdentificationIGDB {
-    pub erreur: ErreurIGDB,
-    pub chemin_str: String
-}
-impl ErreurIdentificationIGDB {
-    pub fn as_err<T>(self) -> Result<T, Erreur> {
-        Err(Erreur::ErreurIdentificationIGDB(self))
-    }
-}
-
-impl TraitErreur for ErreurIdentificationIGDB {
-    fn message(&self) -> String{
-        format!("Impossible d'identifier le jeu {} avec l'API IGDB.", self.chemin_str)
-    }
-
-    fn cause(&self) -> Option<String> {
-        Some(format!("{}", self.erreur.to_string()))
-    }
-}
-
 // Erreur Traduction
 pub struct ErreurTraduction {
     pub erreur: ErreurGPT,
@@ -57,14 +36,17 @@ impl ErreurTraduction {
 
 impl TraitErreur for ErreurTraduction {
     fn message(&self) -> String {
-        format!("Impossible de traduire le jeu.")
+        String::from("Impossible de traduire le jeu.")
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.as_string())
     }
 }
 
 // Enum Erreur
 pub enum Erreur {
     ErreurIdentification(ErreurIdentification),
-    ErreurIdentificationIGDB(ErreurIdentificationIGDB),
     ErreurTraduction(ErreurTraduction)
 }
 
@@ -72,7 +54,6 @@ impl Erreur {
     fn as_trait(&self) -> &dyn TraitErreur {
         match self {
             Erreur::ErreurIdentification(erreur) => erreur,
-            Erreur::ErreurIdentificationIGDB(erreur) => erreur,
             Erreur::ErreurTraduction(erreur) => erreur,
         }
     }
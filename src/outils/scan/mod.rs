@@ -1,14 +1,17 @@
 pub mod err;
 
-use crate::api::openai::objet::ReponseGPT;
 use crate::donnees::igdb::extra::obtenir_catalogue;
 use crate::donnees::igdb::interface::CompatibleSQL;
 use crate::interne::erreurs::TraitErreur;
 use crate::outils::scan::err::*;
 use crate::donnees::{config, objet::*};
-use crate::api::{igdb::*, openai};
+use crate::api::openai;
 use crate::outils::trad::igdb::Traduisible;
+use crate::outils::fournisseurs::{self, FournisseurMetadonnees, Recherche};
+use crate::outils::arriere_plan::{Evenement, Suivi};
+use crate::outils::{bureau, favoris, images_perso, lanceur, natif, parties};
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 fn extensions_valables(config: &config::LudothequeConfig) -> Vec<String> {
@@ -18,6 +21,10 @@ fn extensions_valables(config: &config::LudothequeConfig) -> Vec<String> {
         ext.extend(emulateur.extensions.clone());
     }
 
+    for emulateur in lanceur::emulateurs_integres(config) {
+        ext.extend(emulateur.extensions);
+    }
+
     ext
 }
 
@@ -25,10 +32,8 @@ fn enfants(dossier: String) -> Vec<String> {
     match std::fs::read_dir(Path::new(&dossier)) {
         Ok(valeur) => {
             let mut ret: Vec<String> = vec![];
-            for v in valeur {
-                if v.is_ok() {
-                    ret.push(v.unwrap().path().into_os_string().into_string().unwrap())
-                }
+            for v in valeur.flatten() {
+                ret.push(v.path().into_os_string().into_string().unwrap())
             }
             ret
         },
@@ -52,9 +57,9 @@ fn fini_par(chemin: String, ext: String) -> bool {
     let l = ext.len();
 
     if n > l {
-        return chemin[n-l..n] == ext;
+        chemin[n-l..n] == ext
     } else {
-        return false;
+        false
     }
 }
 
@@ -63,22 +68,29 @@ pub fn trouver_jeux() -> Vec<PathBuf> {
     let config = config::obtenir_config();
 
     let ext = extensions_valables(&config);
-    let limite = match config.profondeur_recherche {
-        Some(l) => l,
-        None => 1,
-    };
+    let limite = config.profondeur_recherche.unwrap_or(1);
 
-    let dossier: String = match config.repertoire_jeux {
-        Some(repertoire) => repertoire.into_os_string().into_string().unwrap(),
+    let dossier: String = match &config.repertoire_jeux {
+        Some(repertoire) => repertoire.display().to_string(),
         None => return vec![],
     };
 
-    let mut queue: Vec<String> = enfants(dossier);
+    let mut queue: Vec<String> = enfants(dossier.clone());
+
+    // Les dossiers natifs hors du répertoire des jeux sont parcourus eux aussi.
+    if config.jeux_natifs {
+        for dossier_natif in &config.dossiers_natifs {
+            if !dossier_natif.starts_with(&dossier) {
+                queue.extend(enfants(dossier_natif.display().to_string()));
+            }
+        }
+    }
+
     let mut profondeurs: Vec<u32> = vec![0; queue.len()];
 
     let mut jeux: Vec<PathBuf> = vec![];
 
-    while queue.len() > 0 {
+    while !queue.is_empty() {
         let chemin: String = match queue.pop() {
             Some(valeur) => valeur,
             None => continue,
@@ -89,7 +101,9 @@ pub fn trouver_jeux() -> Vec<PathBuf> {
             let enfants = enfants(chemin.clone());
             profondeurs.extend(vec![p+1; enfants.len()]);
             queue.extend(enfants);
-        } else {
+        } else if natif::est_jeu_natif(&config, Path::new(&chemin)) {
+            jeux.push(PathBuf::from(chemin.clone()));
+        } else if !lanceur::est_utilitaire_windows(Path::new(&chemin)) {
             for extension in &ext {
                 if fini_par(chemin.clone(), format!(".{}", extension)) {
                     jeux.push(PathBuf::from(chemin.clone()));
@@ -101,7 +115,17 @@ pub fn trouver_jeux() -> Vec<PathBuf> {
     jeux
 }
 
-pub async fn identifier_jeu(chemin: PathBuf, traduire: bool) -> Result<(), Erreur> {
+// Ce que le nom de fichier apprend d'un jeu, avant toute requête à IGDB.
+struct Identification {
+    chemin: PathBuf,
+    chemin_str: String,
+    nom: String,
+    id: Option<u32>,
+    langue: Option<String>,
+}
+
+// Nom, identifiant et langue tirés du nom de fichier, ex: "Zelda [1025,FR].sfc".
+fn decomposer(chemin: PathBuf) -> Result<Identification, Erreur> {
     let chemin_str: String = match chemin.as_path().as_os_str().to_str() {
         Some(valeur) => String::from(valeur),
         None => return ErreurIdentification {
@@ -113,17 +137,6 @@ pub async fn identifier_jeu(chemin: PathBuf, traduire: bool) -> Result<(), Erreu
 
     let mut id_jeu: Option<u32> = None;
 
-    if Jeu::existe(chemin_str.clone()).await.unwrap() {
-        if let Some(id) = Jeu::charger(chemin_str.clone()).await.unwrap().unwrap().jeu {
-            if JeuIGDB::existe(id).await.unwrap() {
-                println!("{} déjà enregistré.", chemin_str);
-                return Ok(());
-            } else {
-                id_jeu = Some(id);
-            }
-        }
-    }
-
     let nom_fichier: String = match chemin_str.rfind(std::path::MAIN_SEPARATOR_STR) {
         Some(debut) => String::from(&chemin_str[debut + 1..chemin_str.len()]),
         None => chemin_str.clone(),
@@ -158,146 +171,351 @@ pub async fn identifier_jeu(chemin: PathBuf, traduire: bool) -> Result<(), Erreu
         }
     }
 
+    let mut nom = String::from(&nom_fichier);
 
-    let jeu_igdb: JeuIGDB;
-    let mut nom_jeu: String;
-
-    if id_jeu.is_none() {
-        let mut nom = String::from(&nom_fichier);
-
-        if gauche.is_some() &&  nom.len() >= chemin_str.len() - gauche.unwrap() {
-            nom = format!("{}{}", &nom[0..nom.len() + gauche.unwrap() - chemin_str.len()], &nom[nom.len() + droite.unwrap() - chemin_str.len() + 1..nom.len()])
+    if let (Some(gauche), Some(droite)) = (gauche, droite) {
+        if nom.len() >= chemin_str.len() - gauche {
+            nom = format!("{}{}", &nom[0..nom.len() + gauche - chemin_str.len()], &nom[nom.len() + droite - chemin_str.len() + 1..nom.len()])
         }
+    }
 
-        nom = match nom.find(".") {
-            Some(indice) => String::from(&nom[0..indice]),
-            None => nom,
-        };
+    nom = match nom.find(".") {
+        Some(indice) => String::from(&nom[0..indice]),
+        None => nom,
+    };
 
-        nom = String::from(nom.trim());
+    nom = match natif::nom_raccourci(&chemin) {
+        Some(valeur) => valeur,
+        None => String::from(nom.trim()),
+    };
 
-        let client = match ClientIGDB::new().await {
-            Ok(valeur) => valeur,
-            Err(erreur) => return ErreurIdentificationIGDB{ erreur, chemin_str }.as_err() ,
-        };
+    Ok(Identification { chemin, chemin_str, nom, id: id_jeu, langue })
+}
 
-        let resultat: Vec<JeuIGDB> = match client.solliciter(requete::requete_trouver_jeu(nom.clone(), None)).await {
-            Ok(resultat) => resultat,
-            Err(erreur) => return ErreurIdentificationIGDB { erreur, chemin_str }.as_err(),
-        };
+// Renvoie None si le jeu est déjà enregistré.
+async fn analyser(chemin: PathBuf) -> Result<Option<Identification>, Erreur> {
+    let mut identification = decomposer(chemin)?;
+    let chemin_str = identification.chemin_str.clone();
 
-        if resultat.len() == 0 {
-            return ErreurIdentification { chemin, desc: "Pas de resultat correspondant au jeu.", erreur: None}.as_err();
+    if Jeu::existe(chemin_str.clone()).await.unwrap() {
+        if let Some(id) = Jeu::charger(chemin_str.clone()).await.unwrap().unwrap().jeu {
+            if JeuIGDB::existe(id).await.unwrap() {
+                println!("{} déjà enregistré.", chemin_str);
+                return Ok(None);
+            } else {
+                identification.id = identification.id.or(Some(id));
+            }
         }
+    }
 
-        jeu_igdb = resultat[0].clone();
-        std::thread::sleep(std::time::Duration::from_millis(250))
-    } else if id_jeu.unwrap() > 0 {
-        let client = match ClientIGDB::new().await {
-            Ok(valeur) => valeur,
-            Err(erreur) => return ErreurIdentificationIGDB{ erreur, chemin_str }.as_err() ,
-        };
+    Ok(Some(identification))
+}
 
-        let resultat: Vec<JeuIGDB> = match client.solliciter(requete::requete_recuperer_jeu(id_jeu.unwrap())).await {
-            Ok(resultat) => resultat,
-            Err(erreur) => return ErreurIdentificationIGDB { erreur, chemin_str }.as_err(),
-        };
+async fn traduire(chemin: &Path, jeu_igdb: &JeuIGDB) -> Result<JeuIGDB, Erreur> {
+    let chemin = chemin.to_path_buf();
 
-        if resultat.len() == 0 {
-            return ErreurIdentification { chemin, desc: "Jeu inexistant (identifiant invalide).", erreur: None}.as_err();
-        }
+    let prompt = match jeu_igdb.prompt() {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurIdentification { chemin, desc: "Impossible construire le prompt.", erreur: Some(erreur.to_string()) }.as_err(),
+    };
 
-        jeu_igdb = resultat[0].clone();
-        std::thread::sleep(std::time::Duration::from_millis(250))
-    } else {
-        jeu_igdb = JeuIGDB::charger(0).await.unwrap().unwrap();
+    let client = match openai::ClientOpenAI::new().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => return ErreurTraduction { erreur }.as_err(),
+    };
+
+    let requete = openai::requete::requete("You do what the user asks and only return json files.".to_string(), prompt);
+
+    let json = match client.solliciter(requete).await {
+        Ok(reponse) => reponse.choices[0].clone().message.content,
+        Err(erreur) => return ErreurIdentification { chemin, desc: "Erreur pendant la requete à OpenAI.", erreur: Some(erreur.to_string()) }.as_err(),
+    };
+
+    let jeu_traduit: JeuIGDB = match serde_json::from_str(&json) {
+        Ok(jeu) => jeu,
+        Err(erreur) => return ErreurIdentification { chemin, desc: "ChatGPT a répondu n'importe quoi.", erreur: Some(erreur.to_string()) }.as_err(),
+    };
+
+    match jeu_traduit.traduire().await {
+        Ok(_) => Ok(jeu_traduit),
+        Err(erreur) => ErreurIdentification { chemin, desc: "Impossible d'enregistrer le jeu traduit.", erreur: Some(erreur.to_string()) }.as_err(),
     }
+}
+
+async fn enregistrer_identification(identification: Identification, jeu_igdb: JeuIGDB, traduire: bool) -> Result<Jeu, Erreur> {
+    let Identification { chemin, chemin_str, nom, langue, .. } = identification;
 
     match jeu_igdb.enregistrer().await {
         Ok(_) => {},
         Err(erreur) => return ErreurIdentification { chemin, desc: "Impossible d'enregistrer le jeu.", erreur: Some(erreur.to_string()) }.as_err(),
     }
 
-    nom_jeu = jeu_igdb.name.clone();
+    let mut nom_jeu: String = jeu_igdb.name.clone();
 
     if traduire {
-        let prompt = match jeu_igdb.prompt() {
-            Ok(valeur) => valeur,
-            Err(erreur) => return ErreurIdentification { chemin, desc: "Impossible construire le prompt.", erreur: Some(erreur.to_string()) }.as_err(),
-        };
+        nom_jeu = self::traduire(&chemin, &jeu_igdb).await?.name;
+    }
+    if jeu_igdb.id == 0 {
+        nom_jeu = nom;
+    }
 
-        let client = match openai::ClientOpenAI::new().await {
-            Ok(valeur) => valeur,
-            Err(erreur) => return ErreurTraduction { erreur }.as_err(),
-        };
+    let jeu = Jeu { jeu: Some(jeu_igdb.id), chemin: chemin_str.clone(), nom: nom_jeu, langue: langue.unwrap_or(String::new()).to_uppercase() };
 
-        let requete = openai::requete::requete("You do what the user asks and only return json files.".to_string(), prompt);
+    // Écrite en dernier et sur place: un échec plus tôt laisse l'entrée précédente intacte.
+    match jeu.remplacer().await {
+        Ok(_) => {},
+        Err(erreur) => return ErreurIdentification { chemin, desc: "Impossible d'enregistrer le jeu dans le catalogue.", erreur: Some(erreur.to_string()) }.as_err(),
+    }
 
-        let json = match client.solliciter(requete).await {
-            Ok(reponse) => reponse.choices[0].clone().message.content,
-            Err(erreur) => return ErreurIdentification { chemin, desc: "Erreur pendant la requete à OpenAI.", erreur: Some(erreur.to_string()) }.as_err(),
-        };
+    println!("{} enregistré.", chemin_str);
 
-        let jeu_traduit: JeuIGDB = match serde_json::from_str(&json) {
-            Ok(jeu) => jeu,
-            Err(erreur) => return ErreurIdentification { chemin, desc: "ChatGPT a répondu n'importe quoi.", erreur: Some(erreur.to_string()) }.as_err(),
+    Ok(jeu)
+}
+
+// Identifie plusieurs jeux à la fois, en consultant les fournisseurs de métadonnées
+// dans l'ordre de la configuration; chacun regroupe ses requêtes.
+pub async fn identifier_jeux(chemins: Vec<PathBuf>, traduire: bool, suivi: &Suivi) {
+    let fournisseurs = fournisseurs::configures();
+
+    let mut par_id: Vec<Identification> = vec![];
+    let mut par_nom: Vec<Identification> = vec![];
+    let mut inconnus: Vec<Identification> = vec![];
+
+    for chemin in chemins {
+        if suivi.est_annule() {
+            return;
+        }
+
+        match analyser(chemin).await {
+            Ok(Some(identification)) => match identification.id {
+                None => par_nom.push(identification),
+                Some(0) => inconnus.push(identification),
+                Some(_) => par_id.push(identification),
+            },
+            Ok(None) => {},
+            Err(erreur) => erreur.afficher_erreur(),
+        }
+    }
+
+    let total = par_id.len() + par_nom.len() + inconnus.len();
+    if total == 0 {
+        return;
+    }
+    suivi.signaler(Evenement::Progression(0, total));
+
+    let mut resultats: Vec<(Identification, Result<JeuIGDB, Erreur>)> = vec![];
+
+    resultats.extend(rechercher_par_id(&fournisseurs, par_id).await);
+    if suivi.est_annule() {
+        return;
+    }
+    resultats.extend(rechercher_par_nom(&fournisseurs, par_nom).await);
+
+    for identification in inconnus {
+        let jeu = JeuIGDB::charger(0).await.unwrap().unwrap();
+        resultats.push((identification, Ok(jeu)));
+    }
+
+    for (faits, (identification, resultat)) in resultats.into_iter().enumerate() {
+        if suivi.est_annule() {
+            return;
+        }
+
+        let resultat = match resultat {
+            Ok(jeu_igdb) => enregistrer_identification(identification, jeu_igdb, traduire).await,
+            Err(erreur) => Err(erreur),
         };
 
-        match jeu_traduit.traduire().await {
-            Ok(_) => {},
-            Err(erreur) => return ErreurIdentification { chemin, desc: "Impossible d'enregistrer le jeu traduit.", erreur: Some(erreur.to_string()) }.as_err(),
+        match resultat {
+            Ok(jeu) => suivi.signaler(Evenement::Jeu(jeu)),
+            Err(erreur) => erreur.afficher_erreur(),
         }
 
-        nom_jeu = jeu_traduit.name.clone();
+        suivi.signaler(Evenement::Progression(faits + 1, total));
     }
-    if jeu_igdb.id == 0 {
-        let mut nom = String::from(nom_fichier);
+}
+
+fn echec(identifications: Vec<Identification>, desc: &'static str, erreur: Option<String>) -> Vec<(Identification, Result<JeuIGDB, Erreur>)> {
+    identifications.into_iter()
+        .map(|identification| {
+            let resultat = ErreurIdentification {
+                chemin: identification.chemin.clone(),
+                desc,
+                erreur: erreur.clone(),
+            }.as_err();
+            (identification, resultat)
+        })
+        .collect()
+}
 
-        if gauche.is_some() &&  nom.len() >= chemin_str.len() - gauche.unwrap() {
-            nom = format!("{}{}", &nom[0..nom.len() + gauche.unwrap() - chemin_str.len()], &nom[nom.len() + droite.unwrap() - chemin_str.len() + 1..nom.len()])
+// Chaque identifiant est demandé au fournisseur qui l'a attribué.
+async fn rechercher_par_id(
+    fournisseurs: &[Box<dyn FournisseurMetadonnees>],
+    identifications: Vec<Identification>
+) -> Vec<(Identification, Result<JeuIGDB, Erreur>)> {
+    let mut resultats: Vec<(Identification, Result<JeuIGDB, Erreur>)> = vec![];
+    let mut restantes = identifications;
+
+    for fournisseur in fournisseurs {
+        let (gerees, autres): (Vec<Identification>, Vec<Identification>) = restantes.into_iter()
+            .partition(|identification| identification.id.is_some_and(|id| fournisseur.gere_id(id)));
+        restantes = autres;
+
+        if gerees.is_empty() {
+            continue;
         }
 
-        nom = match nom.find(".") {
-            Some(indice) => String::from(&nom[0..indice]),
-            None => nom,
+        let mut ids: Vec<u32> = gerees.iter().filter_map(|identification| identification.id).collect();
+        ids.sort();
+        ids.dedup();
+
+        let jeux: HashMap<u32, JeuIGDB> = match fournisseur.recuperer(&ids).await {
+            Ok(liste) => liste.into_iter().map(|jeu| (jeu.id, jeu)).collect(),
+            Err(erreur) => {
+                resultats.extend(echec(gerees, "Fournisseur de métadonnées indisponible.", Some(erreur.to_string())));
+                continue;
+            },
         };
 
-        nom_jeu = String::from(nom.trim());
+        for identification in gerees {
+            let resultat = match identification.id.and_then(|id| jeux.get(&id)) {
+                Some(jeu) => Ok(jeu.clone()),
+                None => ErreurIdentification {
+                    chemin: identification.chemin.clone(),
+                    desc: "Jeu inexistant (identifiant invalide).",
+                    erreur: None,
+                }.as_err(),
+            };
+            resultats.push((identification, resultat));
+        }
     }
 
-    let jeu = Jeu { jeu: Some(jeu_igdb.id), chemin: chemin_str.clone(), nom: nom_jeu, langue: langue.unwrap_or(String::new()).to_uppercase() };
+    resultats.extend(echec(restantes, "Aucun fournisseur configuré pour cet identifiant.", None));
+    resultats
+}
 
-    match jeu.enregistrer().await {
-        Ok(_) => {},
-        Err(erreur) => return ErreurIdentification { chemin, desc: "Impossible d'enregistrer le jeu dans le catalogue.", erreur: Some(erreur.to_string()) }.as_err(),
+// Les jeux sans réponse d'un fournisseur passent au suivant.
+async fn rechercher_par_nom(
+    fournisseurs: &[Box<dyn FournisseurMetadonnees>],
+    identifications: Vec<Identification>
+) -> Vec<(Identification, Result<JeuIGDB, Erreur>)> {
+    let mut resultats: Vec<(Identification, Result<JeuIGDB, Erreur>)> = vec![];
+    let mut restantes = identifications;
+    let mut derniere_erreur: Option<String> = None;
+
+    for fournisseur in fournisseurs {
+        if restantes.is_empty() {
+            break;
+        }
+
+        let recherches: Vec<Recherche> = restantes.iter()
+            .map(|identification| Recherche { chemin: identification.chemin.clone(), nom: identification.nom.clone() })
+            .collect();
+
+        let mut reponses = match fournisseur.rechercher(&recherches).await {
+            Ok(valeur) => valeur.into_iter(),
+            Err(erreur) => {
+                println!("ATTENTION: Fournisseur {} ignoré: {}", fournisseur.nom(), erreur);
+                derniere_erreur = Some(erreur.to_string());
+                continue;
+            },
+        };
+
+        let mut suivantes: Vec<Identification> = vec![];
+
+        for identification in restantes {
+            match reponses.next().flatten() {
+                Some(jeu) => resultats.push((identification, Ok(jeu))),
+                None => suivantes.push(identification),
+            }
+        }
+
+        restantes = suivantes;
     }
 
-    println!("{} enregistré.", chemin_str);
+    resultats.extend(echec(restantes, "Pas de resultat correspondant au jeu.", derniere_erreur));
+    resultats
+}
+
+// Identifie de nouveau un jeu, même déjà enregistré, au besoin avec un identifiant imposé.
+pub async fn reidentifier(chemin: PathBuf, id: Option<u32>, traduire: bool) -> Result<JeuIGDB, Erreur> {
+    let mut identification = decomposer(chemin.clone())?;
+    identification.id = id.or(identification.id);
+
+    let fournisseurs = fournisseurs::configures();
+
+    let resultats = match identification.id {
+        Some(0) => {
+            let inconnu = match JeuIGDB::charger(0).await {
+                Ok(Some(jeu)) => Ok(jeu),
+                _ => ErreurIdentification { chemin: chemin.clone(), desc: "Jeu inconnu absent de la base.", erreur: None }.as_err(),
+            };
+            vec![(identification, inconnu)]
+        },
+        Some(_) => rechercher_par_id(&fournisseurs, vec![identification]).await,
+        None => rechercher_par_nom(&fournisseurs, vec![identification]).await,
+    };
+
+    let (identification, resultat) = match resultats.into_iter().next() {
+        Some(valeur) => valeur,
+        None => return ErreurIdentification { chemin, desc: "Pas de resultat correspondant au jeu.", erreur: None }.as_err(),
+    };
+
+    let jeu_igdb = resultat?;
 
-    Ok(())
+    enregistrer_identification(identification, jeu_igdb.clone(), traduire).await?;
+
+    Ok(jeu_igdb)
 }
 
-pub async fn nettoyer_catalogue() {
+// Traduit un jeu déjà identifié et renomme son entrée du catalogue.
+pub async fn traduire_jeu(chemin: &str) -> Result<JeuIGDB, Erreur> {
+    let jeu = match Jeu::charger(chemin.to_string()).await {
+        Ok(Some(valeur)) => valeur,
+        _ => return ErreurIdentification { chemin: PathBuf::from(chemin), desc: "Jeu absent du catalogue.", erreur: None }.as_err(),
+    };
+
+    let jeu_igdb = match jeu.jeu {
+        Some(id) if id > 0 => JeuIGDB::charger(id).await.ok().flatten(),
+        _ => None,
+    };
+
+    let jeu_igdb = match jeu_igdb {
+        Some(valeur) => valeur,
+        None => return ErreurIdentification { chemin: PathBuf::from(chemin), desc: "Jeu non identifié.", erreur: None }.as_err(),
+    };
+
+    let traduit = traduire(Path::new(chemin), &jeu_igdb).await?;
+
+    match (Jeu { nom: traduit.name.clone(), ..jeu }).remplacer().await {
+        Ok(_) => Ok(traduit),
+        Err(erreur) => ErreurIdentification { chemin: PathBuf::from(chemin), desc: "Impossible d'enregistrer le jeu dans le catalogue.", erreur: Some(erreur.to_string()) }.as_err(),
+    }
+}
+
+pub async fn nettoyer_catalogue(suivi: &Suivi) {
     let jeux = obtenir_catalogue().await;
 
     for jeu in jeux {
         if !std::path::Path::new(&jeu.chemin).exists() {
             println!("{} supprimé.", &jeu.chemin);
-            let _ = Jeu::supprimer(jeu.chemin).await;
+            for type_image in [images_perso::TypeImage::Couverture, images_perso::TypeImage::Illustration] {
+                let _ = images_perso::retirer_image(&jeu.chemin, type_image).await;
+            }
+            favoris::definir_favori(&jeu.chemin, false).await;
+            parties::oublier_parties(&jeu.chemin).await;
+            bureau::retirer_entree(&jeu.chemin);
+            let _ = Jeu::supprimer(jeu.chemin.clone()).await;
+            suivi.signaler(Evenement::Retire(jeu.chemin));
         }
     }
 }
 
-pub async fn scanner() {
-    let nettoyage = nettoyer_catalogue();
+pub async fn scanner(suivi: &Suivi) {
+    let nettoyage = nettoyer_catalogue(suivi);
 
     let liste = trouver_jeux();
     nettoyage.await;
 
-    for chemin in liste {
-        match identifier_jeu(chemin, false).await {
-            Ok(_) => {},
-            Err(erreur) => erreur.afficher_erreur(),
-        }
-    }
+    identifier_jeux(liste, false, suivi).await;
 }
@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use crate::interne::erreurs::TraitErreur;
+
+// Erreur Lecture
+pub struct ErreurLecture {
+    pub erreur: std::io::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurLecture {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurLecture(self))
+    }
+}
+
+impl TraitErreur for ErreurLecture {
+    fn message(&self) -> String {
+        format!("Impossible de lire {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur VDF
+pub struct ErreurVDF {
+    pub erreur: String,
+    pub chemin: PathBuf,
+}
+
+impl ErreurVDF {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurVDF(self))
+    }
+}
+
+impl TraitErreur for ErreurVDF {
+    fn message(&self) -> String {
+        format!("{} n'est pas un fichier de raccourcis Steam valide.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(self.erreur.clone())
+    }
+}
+
+// Erreur Ecriture
+pub struct ErreurEcriture {
+    pub erreur: std::io::Error,
+    pub chemin: PathBuf,
+}
+
+impl ErreurEcriture {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurEcriture(self))
+    }
+}
+
+impl TraitErreur for ErreurEcriture {
+    fn message(&self) -> String {
+        format!("Impossible d'écrire {}.", self.chemin.display())
+    }
+
+    fn cause(&self) -> Option<String> {
+        Some(format!("{}", self.erreur))
+    }
+}
+
+// Erreur Steam Introuvable
+pub struct ErreurSteamIntrouvable {
+    pub dossiers: Vec<PathBuf>,
+}
+
+impl ErreurSteamIntrouvable {
+    pub fn as_err<T>(self) -> Result<T, Erreur> {
+        Err(Erreur::ErreurSteamIntrouvable(self))
+    }
+}
+
+impl TraitErreur for ErreurSteamIntrouvable {
+    fn message(&self) -> String {
+        String::from("Aucun utilisateur Steam trouvé.")
+    }
+
+    fn cause(&self) -> Option<String> {
+        let dossiers: Vec<String> = self.dossiers.iter().map(|dossier| dossier.display().to_string()).collect();
+        Some(format!("Dossiers cherchés: {}", dossiers.join(", ")))
+    }
+}
+
+// Enum Erreur
+pub enum Erreur {
+    ErreurLecture(ErreurLecture),
+    ErreurVDF(ErreurVDF),
+    ErreurEcriture(ErreurEcriture),
+    ErreurSteamIntrouvable(ErreurSteamIntrouvable),
+}
+
+impl Erreur {
+    fn as_trait(&self) -> &dyn TraitErreur {
+        match self {
+            Erreur::ErreurLecture(erreur) => erreur,
+            Erreur::ErreurVDF(erreur) => erreur,
+            Erreur::ErreurEcriture(erreur) => erreur,
+            Erreur::ErreurSteamIntrouvable(erreur) => erreur,
+        }
+    }
+}
+
+impl TraitErreur for Erreur {
+    fn message(&self) -> String {
+        self.as_trait().message()
+    }
+
+    fn cause(&self) -> Option<String> {
+        self.as_trait().cause()
+    }
+}
+
+impl std::fmt::Display for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+impl std::fmt::Debug for Erreur {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
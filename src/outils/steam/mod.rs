@@ -0,0 +1,436 @@
+pub mod err;
+pub mod vdf;
+
+use std::path::{Path, PathBuf};
+
+use crate::donnees::config;
+use crate::donnees::igdb::extra::obtenir_catalogue;
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::igdb::obtenir_db;
+use crate::donnees::objet::{Jeu, JeuIGDB};
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::export;
+use crate::outils::fournisseurs::medias_jeu;
+use crate::outils::images_perso::{image_perso, TypeImage};
+use crate::outils::medias::TypeMedia;
+use crate::outils::steam::err::*;
+use crate::outils::steam::vdf::Valeur;
+
+const ETIQUETTE: &str = "Ludothèque";
+
+// Raccourci « jeu non-Steam » relançant la ludothèque sur un jeu du catalogue.
+pub struct Raccourci {
+    pub appid: u32,
+    pub nom: String,
+    pub exe: String,
+    pub dossier: String,
+    pub options: String,
+    pub couverture: Option<PathBuf>,
+    pub illustration: Option<PathBuf>,
+}
+
+// Steam dérive l'identifiant du raccourci de son exécutable et de son nom; les
+// images de la grille sont nommées d'après lui.
+pub fn identifiant(exe: &str, nom: &str) -> u32 {
+    vdf::crc32(format!("{}{}", exe, nom).as_bytes()) | 0x8000_0000
+}
+
+fn texte(valeur: &str) -> Valeur {
+    Valeur::Texte(String::from(valeur))
+}
+
+impl Raccourci {
+    fn icone(&self) -> String {
+        self.couverture.as_ref().map(|chemin| chemin.display().to_string()).unwrap_or_default()
+    }
+
+    fn entree(&self) -> Valeur {
+        Valeur::Objet(vec![
+            (String::from("appid"), Valeur::Entier(self.appid)),
+            (String::from("AppName"), texte(&self.nom)),
+            (String::from("Exe"), texte(&self.exe)),
+            (String::from("StartDir"), texte(&self.dossier)),
+            (String::from("icon"), texte(&self.icone())),
+            (String::from("ShortcutPath"), texte("")),
+            (String::from("LaunchOptions"), texte(&self.options)),
+            (String::from("IsHidden"), Valeur::Entier(0)),
+            (String::from("AllowDesktopConfig"), Valeur::Entier(1)),
+            (String::from("AllowOverlay"), Valeur::Entier(1)),
+            (String::from("OpenVR"), Valeur::Entier(0)),
+            (String::from("Devkit"), Valeur::Entier(0)),
+            (String::from("DevkitGameID"), texte("")),
+            (String::from("DevkitOverrideAppID"), Valeur::Entier(0)),
+            (String::from("LastPlayTime"), Valeur::Entier(0)),
+            (String::from("FlatpakAppID"), texte("")),
+            (String::from("tags"), Valeur::Objet(vec![(String::from("0"), texte(ETIQUETTE))])),
+        ])
+    }
+
+    // Seuls les champs venant du catalogue sont réécrits: le reste (étiquettes,
+    // dernière partie…) appartient à Steam et à l'utilisateur.
+    fn mettre_a_jour(&self, entree: &mut Valeur) {
+        entree.definir("appid", Valeur::Entier(self.appid));
+        entree.definir("AppName", texte(&self.nom));
+        entree.definir("Exe", texte(&self.exe));
+        entree.definir("StartDir", texte(&self.dossier));
+        entree.definir("icon", texte(&self.icone()));
+        entree.definir("LaunchOptions", texte(&self.options));
+    }
+}
+
+fn appid_entree(entree: &Valeur) -> Option<u32> {
+    if let Some(appid) = entree.champ("appid").and_then(Valeur::entier) {
+        return Some(appid);
+    }
+
+    // Anciens raccourcis, sans appid enregistré.
+    let exe = entree.champ("Exe").and_then(Valeur::texte)?;
+    let nom = entree.champ("AppName").and_then(Valeur::texte)?;
+    Some(identifiant(exe, nom))
+}
+
+// Met à jour les raccourcis déjà présents et ajoute les autres, sans toucher à ceux
+// de l'utilisateur. Renvoie le nombre d'ajouts et de mises à jour.
+pub fn fusionner(racine: &mut Valeur, raccourcis: &[Raccourci]) -> (u32, u32) {
+    if racine.champ("shortcuts").is_none() {
+        racine.definir("shortcuts", Valeur::Objet(vec![]));
+    }
+
+    let entrees = match racine.champ_mut("shortcuts") {
+        Some(Valeur::Objet(valeur)) => valeur,
+        _ => return (0, 0),
+    };
+
+    let (mut ajoutes, mut mis_a_jour) = (0, 0);
+
+    for raccourci in raccourcis {
+        // Un raccourci renommé change d'appid: il est retrouvé par ses options de lancement.
+        let position = entrees.iter().position(|(_, entree)| appid_entree(entree) == Some(raccourci.appid))
+            .or_else(|| entrees.iter().position(|(_, entree)| {
+                entree.champ("LaunchOptions").and_then(Valeur::texte) == Some(raccourci.options.as_str())
+            }));
+
+        match position.and_then(|position| entrees.get_mut(position)) {
+            Some((_, entree)) => {
+                raccourci.mettre_a_jour(entree);
+                mis_a_jour += 1;
+            },
+            None => {
+                entrees.push((String::new(), raccourci.entree()));
+                ajoutes += 1;
+            },
+        }
+    }
+
+    // Steam numérote les raccourcis à partir de 0, sans trou.
+    for (indice, (cle, _)) in entrees.iter_mut().enumerate() {
+        *cle = indice.to_string();
+    }
+
+    (ajoutes, mis_a_jour)
+}
+
+fn dossiers_steam() -> Vec<PathBuf> {
+    let mut dossiers: Vec<PathBuf> = vec![];
+
+    if let Some(steam) = config::obtenir_config().proton.and_then(|proton| proton.steam) {
+        dossiers.push(steam);
+    }
+
+    if let Some(maison) = dirs::home_dir() {
+        dossiers.push(maison.join(".steam/steam"));
+        dossiers.push(maison.join(".local/share/Steam"));
+        dossiers.push(maison.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"));
+    }
+
+    dossiers
+}
+
+// Dossiers userdata/<id>/config des comptes Steam connus (0 est le compte anonyme).
+fn dossiers_utilisateurs() -> Result<Vec<PathBuf>, Erreur> {
+    let mut utilisateurs: Vec<PathBuf> = vec![];
+    let mut vus: Vec<PathBuf> = vec![];
+
+    for steam in dossiers_steam() {
+        let userdata = match std::fs::canonicalize(steam.join("userdata")) {
+            Ok(valeur) if !vus.contains(&valeur) => valeur,
+            _ => continue,
+        };
+
+        if let Ok(entrees) = std::fs::read_dir(&userdata) {
+            for entree in entrees.flatten() {
+                let nom = entree.file_name().to_string_lossy().to_string();
+
+                if nom != "0" && nom.chars().all(|caractere| caractere.is_ascii_digit()) {
+                    utilisateurs.push(entree.path().join("config"));
+                }
+            }
+        }
+
+        vus.push(userdata);
+    }
+
+    match utilisateurs.is_empty() {
+        true => ErreurSteamIntrouvable { dossiers: dossiers_steam() }.as_err(),
+        false => Ok(utilisateurs),
+    }
+}
+
+async fn jeux_collection(collection: &str) -> Vec<Jeu> {
+    let db = match obtenir_db().await {
+        Ok(valeur) => valeur,
+        Err(erreur) => {
+            erreur.afficher_erreur();
+            return vec![];
+        },
+    };
+
+    match sqlx::query_as::<_, Jeu>(
+        "SELECT catalogue.* FROM catalogue
+        JOIN jeux ON catalogue.jeu = jeux.id
+        JOIN collections ON jeux.collection = collections.id
+        WHERE collections.slug = ? OR lower(collections.name) = lower(?) OR lower(collections.name_traduit) = lower(?);"
+    )
+        .bind(collection)
+        .bind(collection)
+        .bind(collection)
+        .fetch_all(&db).await {
+        Ok(liste) => liste,
+        Err(erreur) => {
+            println!("ATTENTION: {}", erreur);
+            vec![]
+        },
+    }
+}
+
+async fn illustration(jeu: &Jeu, igdb: &JeuIGDB) -> Option<PathBuf> {
+    if let Some(image) = image_perso(&jeu.chemin, TypeImage::Illustration).await {
+        return Some(image);
+    }
+
+    medias_jeu(igdb, &jeu.chemin).await
+        .into_iter()
+        .filter(|source| source.type_media() == TypeMedia::Illustration)
+        .find_map(|source| source.chemin_local(TypeMedia::Illustration.taille_defaut()))
+}
+
+async fn raccourci(jeu: &Jeu, exe: &str, dossier: &str) -> Raccourci {
+    let igdb = match jeu.jeu {
+        Some(id) if id > 0 => JeuIGDB::charger_traduit(id).await.ok().flatten(),
+        _ => None,
+    };
+
+    let nom = igdb.as_ref().map(|igdb| igdb.name.clone()).unwrap_or(jeu.nom.clone());
+
+    let (couverture, illustration) = match &igdb {
+        Some(igdb) => (export::couverture(jeu, igdb).await, illustration(jeu, igdb).await),
+        None => (
+            image_perso(&jeu.chemin, TypeImage::Couverture).await,
+            image_perso(&jeu.chemin, TypeImage::Illustration).await,
+        ),
+    };
+
+    Raccourci {
+        appid: identifiant(exe, &nom),
+        nom,
+        exe: String::from(exe),
+        dossier: String::from(dossier),
+        options: format!("lancer \"{}\"", jeu.chemin.replace('"', "\\\"")),
+        couverture,
+        illustration,
+    }
+}
+
+// Deux jeux de même titre (langues, versions) auraient le même appid: leur nom est
+// complété par la langue, puis par le nom de fichier s'il reste ambigu.
+fn departager(raccourcis: &mut [Raccourci], jeux: &[Jeu]) {
+    let doublons = |raccourcis: &[Raccourci]| -> Vec<usize> {
+        (0..raccourcis.len())
+            .filter(|indice| raccourcis.iter().filter(|autre| autre.nom == raccourcis[*indice].nom).count() > 1)
+            .collect()
+    };
+
+    let noms: Vec<String> = raccourcis.iter().map(|raccourci| raccourci.nom.clone()).collect();
+
+    for indice in doublons(raccourcis) {
+        if !jeux[indice].langue.trim().is_empty() {
+            raccourcis[indice].nom = format!("{} ({})", noms[indice], jeux[indice].langue.trim());
+        }
+    }
+
+    for indice in doublons(raccourcis) {
+        let fichier = Path::new(&jeux[indice].chemin)
+            .file_name()
+            .map(|fichier| fichier.to_string_lossy().to_string())
+            .unwrap_or(jeux[indice].chemin.clone());
+        raccourcis[indice].nom = format!("{} ({})", noms[indice], fichier);
+    }
+
+    for raccourci in raccourcis.iter_mut() {
+        raccourci.appid = identifiant(&raccourci.exe, &raccourci.nom);
+    }
+}
+
+// Une image choisie dans Steam n'est jamais remplacée.
+fn copier_image(source: &Path, grille: &Path, nom: &str) {
+    let extension = source.extension().map(|extension| extension.to_string_lossy().to_lowercase()).unwrap_or(String::from("png"));
+
+    if ["png", "jpg", "jpeg"].iter().any(|autre| grille.join(format!("{}.{}", nom, autre)).exists()) {
+        return;
+    }
+
+    if let Err(erreur) = std::fs::copy(source, grille.join(format!("{}.{}", nom, extension))) {
+        println!("ATTENTION: Impossible de copier {} dans la grille Steam: {}", source.display(), erreur);
+    }
+}
+
+fn copier_images(grille: &Path, raccourcis: &[Raccourci]) {
+    if let Err(erreur) = std::fs::create_dir_all(grille) {
+        return println!("ATTENTION: Impossible de créer {}: {}", grille.display(), erreur);
+    }
+
+    for raccourci in raccourcis {
+        if let Some(couverture) = &raccourci.couverture {
+            copier_image(couverture, grille, &format!("{}p", raccourci.appid));
+        }
+        if let Some(illustration) = &raccourci.illustration {
+            copier_image(illustration, grille, &format!("{}_hero", raccourci.appid));
+            copier_image(illustration, grille, &raccourci.appid.to_string());
+        }
+    }
+}
+
+fn enregistrer_raccourcis(dossier: &Path, raccourcis: &[Raccourci]) -> Result<(u32, u32), Erreur> {
+    let fichier = dossier.join("shortcuts.vdf");
+
+    let mut racine = match std::fs::read(&fichier) {
+        Ok(octets) => match vdf::lire(&octets) {
+            Ok(valeur) => valeur,
+            Err(erreur) => return ErreurVDF { erreur, chemin: fichier }.as_err(),
+        },
+        Err(erreur) if erreur.kind() == std::io::ErrorKind::NotFound => Valeur::Objet(vec![]),
+        Err(erreur) => return ErreurLecture { erreur, chemin: fichier }.as_err(),
+    };
+
+    let bilan = fusionner(&mut racine, raccourcis);
+
+    if fichier.exists() {
+        let sauvegarde = dossier.join("shortcuts.vdf.bak");
+        if let Err(erreur) = std::fs::copy(&fichier, &sauvegarde) {
+            return ErreurEcriture { erreur, chemin: sauvegarde }.as_err();
+        }
+    }
+
+    match std::fs::write(&fichier, vdf::ecrire(&racine)) {
+        Ok(_) => {},
+        Err(erreur) => return ErreurEcriture { erreur, chemin: fichier }.as_err(),
+    }
+
+    copier_images(&dossier.join("grid"), raccourcis);
+
+    Ok(bilan)
+}
+
+pub async fn exporter(collection: Option<&str>) -> Result<(), Erreur> {
+    let utilisateurs = dossiers_utilisateurs()?;
+
+    let exe = std::env::current_exe().unwrap_or(PathBuf::from("ludotheque"));
+    let dossier = exe.parent().map(Path::to_path_buf).unwrap_or_default();
+    let (exe, dossier) = (format!("\"{}\"", exe.display()), format!("\"{}\"", dossier.display()));
+
+    let jeux = match collection {
+        Some(nom) => jeux_collection(nom).await,
+        None => obtenir_catalogue().await,
+    };
+
+    let mut raccourcis: Vec<Raccourci> = vec![];
+    for jeu in &jeux {
+        raccourcis.push(raccourci(jeu, &exe, &dossier).await);
+    }
+    departager(&mut raccourcis, &jeux);
+
+    println!("INFO: Steam réécrit shortcuts.vdf en quittant: fermez-le avant l'export.");
+
+    for utilisateur in utilisateurs {
+        let (ajoutes, mis_a_jour) = enregistrer_raccourcis(&utilisateur, &raccourcis)?;
+        println!(
+            "INFO: {} raccourci(s) Steam ajouté(s), {} mis à jour dans {}.",
+            ajoutes,
+            mis_a_jour,
+            utilisateur.display(),
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raccourci(nom: &str) -> Raccourci {
+        Raccourci {
+            appid: identifiant("\"/usr/bin/ludotheque\"", nom),
+            nom: String::from(nom),
+            exe: String::from("\"/usr/bin/ludotheque\""),
+            dossier: String::from("\"/usr/bin\""),
+            options: format!("lancer \"/jeux/{}\"", nom),
+            couverture: None,
+            illustration: None,
+        }
+    }
+
+    #[test]
+    fn fusion_conserve_les_raccourcis() {
+        let perso = Valeur::Objet(vec![
+            (String::from("AppName"), texte("Firefox")),
+            (String::from("Exe"), texte("\"/usr/bin/firefox\"")),
+        ]);
+
+        let mut ancien = raccourci("Zelda").entree();
+        ancien.definir("LastPlayTime", Valeur::Entier(1700000000));
+        ancien.definir("LaunchOptions", texte("ancien"));
+
+        let mut racine = Valeur::Objet(vec![(
+            String::from("shortcuts"),
+            Valeur::Objet(vec![(String::from("0"), perso.clone()), (String::from("1"), ancien)]),
+        )]);
+
+        assert_eq!(fusionner(&mut racine, &[raccourci("Zelda"), raccourci("Metroid")]), (1, 1));
+
+        let entrees = racine.champ("shortcuts").unwrap();
+        assert_eq!(entrees.champ("0"), Some(&perso));
+
+        let zelda = entrees.champ("1").unwrap();
+        assert_eq!(zelda.champ("LastPlayTime"), Some(&Valeur::Entier(1700000000)));
+        assert_eq!(zelda.champ("LaunchOptions"), Some(&texte("lancer \"/jeux/Zelda\"")));
+
+        assert_eq!(entrees.champ("2").and_then(|metroid| metroid.champ("AppName")), Some(&texte("Metroid")));
+    }
+
+    #[test]
+    fn titres_en_double() {
+        let jeu = |chemin: &str, langue: &str| Jeu {
+            jeu: None,
+            chemin: String::from(chemin),
+            nom: String::from("Zelda"),
+            langue: String::from(langue),
+        };
+        let jeux = vec![
+            jeu("/jeux/Zelda (FR).sfc", "FR"),
+            jeu("/jeux/Zelda (US).sfc", "US"),
+            jeu("/jeux/Zelda.sfc", ""),
+            jeu("/jeux/Zelda (FR) (Rev 1).sfc", "FR"),
+        ];
+        let mut raccourcis = vec![raccourci("Zelda"), raccourci("Zelda"), raccourci("Zelda"), raccourci("Zelda")];
+
+        departager(&mut raccourcis, &jeux);
+
+        // La langue suffit à distinguer US et le jeu sans langue; pas les deux FR.
+        assert_eq!(raccourcis[0].nom, "Zelda (Zelda (FR).sfc)");
+        assert_eq!(raccourcis[1].nom, "Zelda (US)");
+        assert_eq!(raccourcis[2].nom, "Zelda");
+        assert_eq!(raccourcis[3].nom, "Zelda (Zelda (FR) (Rev 1).sfc)");
+        assert_ne!(raccourcis[0].appid, raccourcis[3].appid);
+    }
+}
@@ -0,0 +1,210 @@
+// VDF binaire de Steam (shortcuts.vdf): une suite de champs typés, chacun précédé
+// d'un octet de type et de son nom terminé par un zéro. Un objet se ferme par 0x08.
+
+const OBJET: u8 = 0x00;
+const TEXTE: u8 = 0x01;
+const ENTIER: u8 = 0x02;
+const FLOTTANT: u8 = 0x03;
+const ENTIER64: u8 = 0x07;
+const FIN: u8 = 0x08;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Valeur {
+    Objet(Vec<(String, Valeur)>),
+    Texte(String),
+    Entier(u32),
+    Flottant(f32),
+    Entier64(u64),
+}
+
+impl Valeur {
+    pub fn champ(&self, nom: &str) -> Option<&Valeur> {
+        match self {
+            Valeur::Objet(champs) => champs.iter()
+                .find(|(cle, _)| cle.eq_ignore_ascii_case(nom))
+                .map(|(_, valeur)| valeur),
+            _ => None,
+        }
+    }
+
+    pub fn champ_mut(&mut self, nom: &str) -> Option<&mut Valeur> {
+        match self {
+            Valeur::Objet(champs) => champs.iter_mut()
+                .find(|(cle, _)| cle.eq_ignore_ascii_case(nom))
+                .map(|(_, valeur)| valeur),
+            _ => None,
+        }
+    }
+
+    // Remplace la valeur du champ, ou l'ajoute à la fin de l'objet.
+    pub fn definir(&mut self, nom: &str, valeur: Valeur) {
+        if let Valeur::Objet(champs) = self {
+            match champs.iter_mut().find(|(cle, _)| cle.eq_ignore_ascii_case(nom)) {
+                Some((_, ancienne)) => *ancienne = valeur,
+                None => champs.push((String::from(nom), valeur)),
+            }
+        }
+    }
+
+    pub fn entier(&self) -> Option<u32> {
+        match self {
+            Valeur::Entier(valeur) => Some(*valeur),
+            _ => None,
+        }
+    }
+
+    pub fn texte(&self) -> Option<&str> {
+        match self {
+            Valeur::Texte(valeur) => Some(valeur),
+            _ => None,
+        }
+    }
+}
+
+struct Lecteur<'a> {
+    octets: &'a [u8],
+    position: usize,
+}
+
+impl Lecteur<'_> {
+    fn octet(&mut self) -> Result<u8, String> {
+        let octet = *self.octets.get(self.position).ok_or("fin de fichier inattendue")?;
+        self.position += 1;
+        Ok(octet)
+    }
+
+    fn octets<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let tranche = self.octets.get(self.position..self.position + N).ok_or("fin de fichier inattendue")?;
+        self.position += N;
+        Ok(tranche.try_into().unwrap_or([0; N]))
+    }
+
+    fn texte(&mut self) -> Result<String, String> {
+        let reste = &self.octets[self.position.min(self.octets.len())..];
+        let fin = reste.iter().position(|octet| *octet == 0).ok_or("texte non terminé")?;
+        self.position += fin + 1;
+        Ok(String::from_utf8_lossy(&reste[..fin]).to_string())
+    }
+
+    // Lit les champs jusqu'au 0x08 qui ferme l'objet (ou la fin du fichier à la racine).
+    fn objet(&mut self, racine: bool) -> Result<Vec<(String, Valeur)>, String> {
+        let mut champs: Vec<(String, Valeur)> = vec![];
+
+        loop {
+            if racine && self.position >= self.octets.len() {
+                return Ok(champs);
+            }
+
+            let type_champ = self.octet()?;
+            if type_champ == FIN {
+                return Ok(champs);
+            }
+
+            let nom = self.texte()?;
+            let valeur = match type_champ {
+                OBJET => Valeur::Objet(self.objet(false)?),
+                TEXTE => Valeur::Texte(self.texte()?),
+                ENTIER => Valeur::Entier(u32::from_le_bytes(self.octets()?)),
+                FLOTTANT => Valeur::Flottant(f32::from_le_bytes(self.octets()?)),
+                ENTIER64 => Valeur::Entier64(u64::from_le_bytes(self.octets()?)),
+                autre => return Err(format!("type {:#04x} inconnu pour {}", autre, nom)),
+            };
+
+            champs.push((nom, valeur));
+        }
+    }
+}
+
+pub fn lire(octets: &[u8]) -> Result<Valeur, String> {
+    Lecteur { octets, position: 0 }.objet(true).map(Valeur::Objet)
+}
+
+fn ecrire_champs(octets: &mut Vec<u8>, champs: &[(String, Valeur)]) {
+    for (nom, valeur) in champs {
+        let type_champ = match valeur {
+            Valeur::Objet(_) => OBJET,
+            Valeur::Texte(_) => TEXTE,
+            Valeur::Entier(_) => ENTIER,
+            Valeur::Flottant(_) => FLOTTANT,
+            Valeur::Entier64(_) => ENTIER64,
+        };
+
+        octets.push(type_champ);
+        octets.extend(nom.as_bytes());
+        octets.push(0);
+
+        match valeur {
+            Valeur::Objet(enfants) => {
+                ecrire_champs(octets, enfants);
+                octets.push(FIN);
+            },
+            Valeur::Texte(texte) => {
+                octets.extend(texte.as_bytes());
+                octets.push(0);
+            },
+            Valeur::Entier(entier) => octets.extend(entier.to_le_bytes()),
+            Valeur::Flottant(flottant) => octets.extend(flottant.to_le_bytes()),
+            Valeur::Entier64(entier) => octets.extend(entier.to_le_bytes()),
+        }
+    }
+}
+
+pub fn ecrire(racine: &Valeur) -> Vec<u8> {
+    let mut octets: Vec<u8> = vec![];
+
+    if let Valeur::Objet(champs) = racine {
+        ecrire_champs(&mut octets, champs);
+    }
+    octets.push(FIN);
+
+    octets
+}
+
+// CRC-32 (IEEE), dont Steam dérive l'identifiant des raccourcis.
+pub fn crc32(octets: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+
+    for octet in octets {
+        crc ^= *octet as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xEDB8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn somme_controle() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn aller_retour() {
+        let racine = Valeur::Objet(vec![(
+            String::from("shortcuts"),
+            Valeur::Objet(vec![(
+                String::from("0"),
+                Valeur::Objet(vec![
+                    (String::from("appid"), Valeur::Entier(0x8123_4567)),
+                    (String::from("AppName"), Valeur::Texte(String::from("Pokémon Rouge"))),
+                    (String::from("LastPlayTime"), Valeur::Entier(0)),
+                    (String::from("tags"), Valeur::Objet(vec![])),
+                ]),
+            )]),
+        )]);
+
+        let octets = ecrire(&racine);
+
+        assert_eq!(octets[..11], *b"\x00shortcuts\x00");
+        assert_eq!(octets[octets.len() - 3..], [FIN, FIN, FIN]);
+        assert_eq!(lire(&octets), Ok(racine));
+    }
+}
@@ -0,0 +1,206 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::igdb::{requete, ClientIGDB};
+use crate::api::igdb::err::Erreur;
+use crate::api::igdb::requete::Endpoint;
+use crate::chemin::{chemins, json};
+use crate::donnees::config;
+use crate::donnees::igdb::extra::{obtenir_catalogue, obtenir_plateformes_catalogue};
+use crate::donnees::igdb::interface::CompatibleSQL;
+use crate::donnees::objet::{EntrepriseIGDB, Jeu, JeuIGDB, PlateformeIGDB};
+use crate::interne::erreurs::TraitErreur;
+use crate::outils::arriere_plan::{Evenement, Suivi};
+use crate::outils::fournisseurs::local;
+use crate::outils::import;
+
+fn jeux_igdb(catalogue: &[Jeu]) -> Vec<u32> {
+    let mut ids: Vec<u32> = catalogue
+        .iter()
+        .filter_map(|jeu| jeu.jeu)
+        .filter(|id| *id > 0 && !local::est_local(*id))
+        .collect();
+
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+// Recharge les jeux du catalogue modifiés sur IGDB depuis leur enregistrement. Les
+// entrées du catalogue concernées sont signalées pour que l'interface les redessine.
+pub async fn rafraichir_jeux(client: &ClientIGDB, suivi: &Suivi) -> Result<(), Erreur> {
+    let catalogue = obtenir_catalogue().await;
+    let ids = jeux_igdb(&catalogue);
+
+    let jeux: Vec<JeuIGDB> = client.solliciter_par_lots(&ids, requete::requete_recuperer_jeux).await?;
+
+    let total = jeux.len();
+    let mut modifies = 0;
+
+    suivi.signaler(Evenement::Progression(0, total));
+
+    for (faits, mut jeu) in jeux.into_iter().enumerate() {
+        if suivi.est_annule() {
+            return Ok(());
+        }
+
+        suivi.signaler(Evenement::Progression(faits + 1, total));
+
+        if let Ok(Some(ancien)) = JeuIGDB::charger(jeu.id).await {
+            if ancien.updated_at == jeu.updated_at {
+                continue;
+            }
+
+            // Les champs importés d'un autre lanceur priment sur IGDB.
+            let champs = import::champs_importes(jeu.id).await;
+            import::conserver_importes(&mut jeu, &ancien, &champs);
+
+            jeu.name_traduit = ancien.name_traduit;
+            jeu.summary_traduit = ancien.summary_traduit;
+            jeu.storyline_traduit = ancien.storyline_traduit;
+        }
+
+        match jeu.remplacer().await {
+            Ok(_) => modifies += 1,
+            Err(erreur) => {
+                erreur.afficher_attention();
+                continue;
+            },
+        }
+
+        for entree in catalogue.iter().filter(|entree| entree.jeu == Some(jeu.id)) {
+            suivi.signaler(Evenement::Jeu(entree.clone()));
+        }
+    }
+
+    println!("INFO: {} jeu(x) mis à jour sur {}.", modifies, ids.len());
+
+    Ok(())
+}
+
+// Plateformes des jeux du catalogue et des émulateurs configurés.
+pub async fn synchroniser_plateformes(client: &ClientIGDB, suivi: &Suivi) -> Result<(), Erreur> {
+    let mut ids = obtenir_plateformes_catalogue().await;
+
+    for emulateur in config::obtenir_config().emulateurs {
+        ids.extend(emulateur.plateformes);
+    }
+
+    ids.sort();
+    ids.dedup();
+
+    let plateformes: Vec<PlateformeIGDB> = client.solliciter_par_lots(&ids, requete::requete_recuperer_plateformes).await?;
+
+    let mut modifiees = 0;
+
+    for mut plateforme in plateformes {
+        if suivi.est_annule() {
+            return Ok(());
+        }
+
+        if let Ok(Some(ancienne)) = PlateformeIGDB::charger(plateforme.id).await {
+            if ancienne.updated_at == plateforme.updated_at {
+                continue;
+            }
+
+            plateforme.name_traduit = ancienne.name_traduit;
+            plateforme.summary_traduit = ancienne.summary_traduit;
+        }
+
+        match plateforme.remplacer().await {
+            Ok(_) => modifiees += 1,
+            Err(erreur) => erreur.afficher_attention(),
+        }
+    }
+
+    println!("INFO: {} plateforme(s) mise(s) à jour.", modifiees);
+
+    Ok(())
+}
+
+// Entreprises ayant développé ou édité un jeu du catalogue.
+pub async fn synchroniser_entreprises(client: &ClientIGDB, suivi: &Suivi) -> Result<(), Erreur> {
+    let ids = jeux_igdb(&obtenir_catalogue().await);
+    let mut entreprises: Vec<EntrepriseIGDB> = vec![];
+
+    for lot in ids.chunks(requete::TAILLE_PAGE as usize) {
+        if suivi.est_annule() {
+            return Ok(());
+        }
+
+        entreprises.extend(client.paginer(Endpoint::Entreprises, requete::corps_entreprises_jeux(lot)).await?);
+    }
+
+    entreprises.sort_by_key(|entreprise| entreprise.id);
+    entreprises.dedup_by_key(|entreprise| entreprise.id);
+
+    let mut modifiees = 0;
+
+    for mut entreprise in entreprises {
+        if suivi.est_annule() {
+            return Ok(());
+        }
+
+        if let Ok(Some(ancienne)) = EntrepriseIGDB::charger(entreprise.id).await {
+            if ancienne.updated_at == entreprise.updated_at {
+                continue;
+            }
+
+            entreprise.name_traduit = ancienne.name_traduit;
+            entreprise.description_traduit = ancienne.description_traduit;
+        }
+
+        match entreprise.remplacer().await {
+            Ok(_) => modifiees += 1,
+            Err(erreur) => erreur.afficher_attention(),
+        }
+    }
+
+    println!("INFO: {} entreprise(s) mise(s) à jour.", modifiees);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Synchronisation {
+    pub references: u64,
+}
+
+fn maintenant() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duree| duree.as_secs()).unwrap_or(0)
+}
+
+pub async fn synchroniser(suivi: &Suivi) -> Result<(), Erreur> {
+    let client = ClientIGDB::partage().await?;
+
+    rafraichir_jeux(client, suivi).await?;
+    synchroniser_plateformes(client, suivi).await?;
+    synchroniser_entreprises(client, suivi).await
+}
+
+// Fiches des jeux, plateformes et entreprises: elles changent peu, et au démarrage
+// ne sont resynchronisées qu'une fois la durée du cache IGDB écoulée.
+pub async fn synchroniser_au_demarrage(suivi: &Suivi) {
+    let duree = config::obtenir_config().igdb.duree_cache * 24 * 60 * 60;
+    let nom = String::from("synchronisation.json");
+
+    if let Ok(derniere) = json::charger_json_xdg::<Synchronisation>(nom.clone(), chemins::XDG::CACHE) {
+        if maintenant() < derniere.references + duree {
+            return println!("INFO: Fiches IGDB déjà synchronisées récemment.");
+        }
+    }
+
+    if let Err(erreur) = synchroniser(suivi).await {
+        return erreur.afficher_erreur();
+    }
+
+    // Interrompue, la synchronisation reprendra au prochain démarrage.
+    if suivi.est_annule() {
+        return;
+    }
+
+    if let Err(erreur) = json::enregistrer_json_xdg(Synchronisation { references: maintenant() }, nom, chemins::XDG::CACHE) {
+        erreur.afficher_attention();
+    }
+}
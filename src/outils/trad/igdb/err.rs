@@ -13,7 +13,7 @@ impl ErreurCreationPrompt {
 
 impl TraitErreur for ErreurCreationPrompt {
     fn message(&self) -> String {
-        format!("Une erreur est survenue lors de la création du prompt de traduction.")
+        String::from("Une erreur est survenue lors de la création du prompt de traduction.")
     }
 
     fn cause(&self) -> Option<String> {
@@ -8,7 +8,7 @@ use serde::Serialize;
 
 pub trait Traduisible where Self: Clone + Serialize {
     fn pre_prompt(&self) -> String {
-        format!("")
+        String::new()
     }
 
     fn preparer_traduction(&self) -> Self;
@@ -45,10 +45,11 @@ impl Traduisible for JeuIGDB {
         copie.name_traduit = None;
         //copie.slug_traduit = None;
         if copie.alternative_names.is_some() {
-            let vec = copie.alternative_names.to_owned().unwrap();
-            for i in 0..vec.len() {
-                copie.alternative_names.to_owned().unwrap()[i] = vec[i].preparer_traduction();
+            let mut vec = copie.alternative_names.to_owned().unwrap();
+            for valeur in vec.iter_mut() {
+                *valeur = valeur.preparer_traduction();
             }
+            copie.alternative_names = Some(vec);
         }
         copie.storyline_traduit = None;
         copie.summary_traduit = None;
@@ -75,22 +76,22 @@ impl Traduisible for JeuIGDB {
 
         if copie.genres.is_some() {
             let mut vec = copie.genres.to_owned().unwrap();
-            for i in 0..vec.len() {
-                vec[i] = vec[i].preparer_traduction();
+            for valeur in vec.iter_mut() {
+                *valeur = valeur.preparer_traduction();
             }
             copie.genres = Some(vec);
         }
         if copie.themes.is_some() {
             let mut vec = copie.themes.to_owned().unwrap();
-            for i in 0..vec.len() {
-                vec[i] = vec[i].preparer_traduction();
+            for valeur in vec.iter_mut() {
+                *valeur = valeur.preparer_traduction();
             }
             copie.themes = Some(vec);
         }
         if copie.keywords.is_some() {
             let mut vec = copie.keywords.to_owned().unwrap();
-            for i in 0..vec.len() {
-                vec[i] = vec[i].preparer_traduction();
+            for valeur in vec.iter_mut() {
+                *valeur = valeur.preparer_traduction();
             }
             copie.keywords = Some(vec);
         }
@@ -109,8 +110,8 @@ impl Traduisible for JeuIGDB {
         copie.screenshots = None;
         if copie.videos.is_some() {
             let mut vec = copie.videos.to_owned().unwrap();
-            for i in 0..vec.len() {
-                vec[i] = vec[i].preparer_traduction();
+            for valeur in vec.iter_mut() {
+                *valeur = valeur.preparer_traduction();
             }
             copie.videos = Some(vec);
         }